@@ -2,403 +2,342 @@
 #![allow(clippy::semicolon_if_nothing_returned)]
 #![allow(clippy::float_cmp)]
 
-use crate::{tokens::ColorTokens, ApplyTo};
-use egui::{
-    style::{TextCursorStyle, WidgetVisuals},
-    Color32, Context, Id, Rounding, Stroke, Style, Ui,
+use crate::{
+    easing::Easing, interpolation::Interpolation, mapping::TokenMapping, shape::StyleShape,
+    tokens::ColorTokens, ApplyTo,
 };
+use egui::{Color32, Context, Id, Style};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-#[allow(clippy::many_single_char_names)]
-fn interpolate_color(start: Color32, end: Color32, interpolation: f32) -> Color32 {
-    let r = egui::lerp(f32::from(start.r())..=f32::from(end.r()), interpolation) as u8;
-    let g = egui::lerp(f32::from(start.g())..=f32::from(end.g()), interpolation) as u8;
-    let b = egui::lerp(f32::from(start.b())..=f32::from(end.b()), interpolation) as u8;
-    let a = egui::lerp(f32::from(start.a())..=f32::from(end.a()), interpolation) as u8;
-    Color32::from_rgba_premultiplied(r, g, b, a)
+// the progress of a step of the scale, later steps start later by a fraction of the animation time
+fn staggered(progress: f32, stagger: f32, step: usize) -> f32 {
+    let delay = stagger * step as f32 / 11.;
+    ((progress - delay) / (1. - stagger)).clamp(0., 1.)
 }
 
-#[derive(Debug, Default, Clone)]
+// the id of the animation value in the egui `Context`,
+// a clone gets an id of its own unless the app fixed it
+#[derive(Debug)]
+struct AnimationId {
+    id: Id,
+    fixed: bool,
+    // the animation value exists in the `Context`
+    registered: bool,
+}
+
+impl AnimationId {
+    fn unique() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            id: Id::new("Color animator").with(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            fixed: false,
+            registered: false,
+        }
+    }
+}
+
+impl Clone for AnimationId {
+    fn clone(&self) -> Self {
+        if self.fixed {
+            Self { ..*self }
+        } else {
+            Self::unique()
+        }
+    }
+}
+
+/// Transitions from the colors that are shown to new tokens.
+///
+/// A [`crate::Colorix`] drives its animator with the egui `Context`. Without a context,
+/// a transition is started with [`Self::begin`] and driven with [`Self::advance`] or [`Self::sample`],
+/// e.g. from a custom scheduler.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{animator::ColorAnimator, tokens::ColorTokens, utils::{COOL, WARM}};
+/// let warm = ColorTokens::from_theme(&WARM, true);
+/// let cool = ColorTokens::from_theme(&COOL, true);
+/// let mut animator = ColorAnimator::new(&warm);
+/// animator.set_time(1.0);
+///
+/// animator.begin();
+/// let halfway = animator.advance(0.5, &cool);
+/// assert_ne!(halfway.ui_element_background(), cool.ui_element_background());
+/// assert_eq!(animator.sample(0.5, &cool), halfway);
+///
+/// assert_eq!(animator.advance(0.5, &cool), cool);
+/// assert!(!animator.is_animating());
+/// assert!(animator.take_finished());
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
 pub struct ColorAnimator {
-    pub(crate) anim_id: Option<Id>,
+    id: AnimationId,
     pub progress: f32,
     animation_time: f32,
-    token_shift: bool,
+    interpolation: Interpolation,
+    easing: Easing,
+    stagger: f32,
     pub(crate) animation_done: bool,
     animation_in_progress: bool,
-    values_1: [Color32; 3],
-    values_2: [Color32; 3],
-    pub tokenshifts: [Color32; 3],
+    // lifecycle events that haven't been taken yet
+    started: bool,
+    finished: bool,
     tokens: ColorTokens,
     pub animated_tokens: ColorTokens,
+    // the shadow at the start of the animation and the one currently shown,
+    // light and dark mode derive the shadow from different tokens
+    start_shadow: Color32,
     shadow: Color32,
-    s1: Color32,
-    s2: Color32,
     pub(crate) apply_to: ApplyTo,
 }
 
+impl Default for ColorAnimator {
+    fn default() -> Self {
+        Self::new(&ColorTokens::default())
+    }
+}
+
 impl ColorAnimator {
-    pub(crate) const fn new(tokens: ColorTokens) -> Self {
+    /// An animator that shows `tokens`, with an animation time of 2.3 seconds.
+    /// Every animator gets a unique id, a clone included.
+    #[must_use]
+    pub fn new(tokens: &ColorTokens) -> Self {
         Self {
-            anim_id: None,
+            id: AnimationId::unique(),
             progress: 0.0,
             animation_time: 2.3,
-            token_shift: true,
+            interpolation: Interpolation::Srgb,
+            easing: Easing::Linear,
+            stagger: 0.0,
             animation_done: true,
             animation_in_progress: false,
-            values_1: [Color32::TRANSPARENT; 3],
-            values_2: [Color32::TRANSPARENT; 3],
-            tokenshifts: [Color32::TRANSPARENT; 3],
-            tokens,
-            animated_tokens: tokens,
+            started: false,
+            finished: false,
+            tokens: *tokens,
+            animated_tokens: *tokens,
+            start_shadow: Color32::TRANSPARENT,
             shadow: Color32::TRANSPARENT,
-            s1: Color32::from_black_alpha(25),
-            s2: Color32::from_black_alpha(96),
             apply_to: ApplyTo::Global,
         }
     }
 
     pub(crate) fn set_animate(
         &mut self,
-        ctx: Option<&Context>,
-        ui: Option<&mut Ui>,
-        tokens: ColorTokens,
+        ctx: &Context,
+        tokens: &ColorTokens,
+        shape: &StyleShape,
+        mapping: &TokenMapping,
     ) {
-        if let Some(ctx) = ctx {
-            if self.anim_id.is_none() {
-                self.create_id(ctx);
-            } else {
-                self.animate(Some(ctx), None, tokens);
-            }
-        } else if let Some(ui) = ui {
-            if self.anim_id.is_none() {
-                let anim_id = ui.id();
-                self.anim_id = Some(anim_id);
-                ui.ctx().animate_value_with_time(anim_id, 0.0, 0.0);
-            } else {
-                self.animate(None, Some(ui), tokens);
-            }
+        if self.id.registered {
+            self.animate(ctx, tokens, shape, mapping);
+        } else {
+            self.register(ctx);
         }
     }
-    pub(crate) fn set_time(&mut self, new_time: f32) {
+    /// Sets the duration of a transition in seconds.
+    pub const fn set_time(&mut self, new_time: f32) {
         self.animation_time = new_time;
     }
-    pub(crate) fn create_id(&mut self, ctx: &Context) {
-        let anim_id = egui::Id::new("Color animator");
-        ctx.animate_value_with_time(anim_id, 0.0, 0.0);
-        self.anim_id = Some(anim_id);
+    pub(crate) const fn animation_time(&self) -> f32 {
+        self.animation_time
+    }
+    pub(crate) const fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+    pub const fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+    pub(crate) const fn easing(&self) -> &Easing {
+        &self.easing
+    }
+    pub(crate) const fn stagger(&self) -> f32 {
+        self.stagger
+    }
+    /// Delays the later steps of the scale by a fraction of the animation time, from 0.0 to 0.9.
+    pub const fn set_stagger(&mut self, stagger: f32) {
+        self.stagger = stagger.clamp(0.0, 0.9);
+    }
+    // the id supplied by the app, shared with clones
+    pub(crate) const fn set_id(&mut self, id: Id) {
+        self.id = AnimationId {
+            id,
+            fixed: true,
+            registered: false,
+        };
+    }
+    pub(crate) fn id(&mut self, ctx: &Context) -> Id {
+        if !self.id.registered {
+            self.register(ctx);
+        }
+        self.id.id
+    }
+    // the id set with `Colorix::animation_id`
+    pub(crate) const fn fixed_id(&self) -> Option<Id> {
+        if self.id.fixed {
+            Some(self.id.id)
+        } else {
+            None
+        }
+    }
+    fn register(&mut self, ctx: &Context) {
+        ctx.animate_value_with_time(self.id.id, 0.0, 0.0);
+        self.id.registered = true;
     }
     pub(crate) fn start(&mut self, ctx: &Context) {
+        if self.id.registered {
+            ctx.animate_value_with_time(self.id.id, 0.0, 0.0);
+        }
+        self.begin();
+    }
+    /// Starts a transition from the colors that are shown, without a `Context`.
+    /// An interrupted transition continues from where it was.
+    pub fn begin(&mut self) {
+        // an interrupted animation continues from the colors and the shadow that are shown
+        if self.animation_in_progress {
+            self.tokens = self.animated_tokens;
+            self.start_shadow = self.shadow;
+        } else {
+            self.start_shadow = self.tokens.shadow();
+        }
         self.animation_done = false;
+        self.animation_in_progress = true;
+        self.progress = 0.0;
+        self.started = true;
+    }
+    /// Advances the running transition towards `tokens` by `dt` seconds and returns the colors to show.
+    pub fn advance(&mut self, dt: f32, tokens: &ColorTokens) -> ColorTokens {
+        self.sample(self.progress.mul_add(self.animation_time, dt), tokens)
+    }
+    /// Moves the running transition towards `tokens` to `t` seconds after its start
+    /// and returns the colors to show.
+    pub fn sample(&mut self, t: f32, tokens: &ColorTokens) -> ColorTokens {
+        if self.animation_in_progress {
+            let progress = if self.animation_time > 0. {
+                (t / self.animation_time).clamp(0., 1.)
+            } else {
+                1.
+            };
+            self.step(progress, tokens);
+        }
+        self.animated_tokens
+    }
+    /// Whether a transition is running.
+    #[must_use]
+    pub const fn is_animating(&self) -> bool {
+        self.animation_in_progress
+    }
+    /// The progress of the running transition from 0.0 to 1.0, before easing.
+    /// 1.0 when no transition is running.
+    #[must_use]
+    pub const fn normalized_progress(&self) -> f32 {
         if self.animation_in_progress {
-            self.restart(ctx);
+            self.progress
         } else {
-            self.animation_done = false;
-            self.animation_in_progress = true;
+            1.0
         }
     }
+    /// Returns `true` once after a transition started.
+    pub const fn take_started(&mut self) -> bool {
+        std::mem::replace(&mut self.started, false)
+    }
+    /// Returns `true` once after a transition finished,
+    /// e.g. to capture a screenshot or to resume expensive redraws.
+    ///
+    /// A transition that is interrupted by a new one doesn't finish.
+    pub const fn take_finished(&mut self) -> bool {
+        std::mem::replace(&mut self.finished, false)
+    }
     // if animation in progress and needs a restart
     pub fn restart(&mut self, ctx: &Context) {
-        self.animation_done = true;
-        self.animation_in_progress = false;
-        self.tokens = self.animated_tokens;
-        if let Some(anim_id) = self.anim_id {
-            ctx.animate_value_with_time(anim_id, 0.0, 0.0);
-        }
-        self.token_shift = !self.token_shift;
         self.start(ctx);
     }
 
     pub(crate) fn animate(
         &mut self,
-        ctx: Option<&Context>,
-        ui: Option<&mut Ui>,
-        tokens: ColorTokens,
+        ctx: &Context,
+        tokens: &ColorTokens,
+        shape: &StyleShape,
+        mapping: &TokenMapping,
     ) {
+        if !self.animation_done {
+            self.tick(ctx, tokens);
+            ctx.style_mut(|style| self.set_egui_animation(style, shape, mapping));
+        }
+    }
+    // advances the animation with the clock of the context, without applying it
+    pub(crate) fn tick(&mut self, ctx: &Context, tokens: &ColorTokens) {
         if self.animation_done {
-            match self.apply_to {
-                #[allow(clippy::needless_return)]
-                ApplyTo::Global | ApplyTo::ExtraScale => return,
-                // if animation done, local ui needs to keep updating every frame
-                ApplyTo::Local => {
-                    if let Some(ui) = ui {
-                        // let shadow = if ui.style_mut().visuals.dark_mode {
-                        //     self.s2
-                        // } else {
-                        //     self.s1
-                        // };
-                        self.apply_local_ui(ui.style_mut());
-                    }
-                }
-            }
-        } else {
-            let Some(anim_id) = self.anim_id else { return };
-
-            if let Some(ctx) = ctx {
-                let shadow = if ctx.style().visuals.dark_mode {
-                    self.s2
-                } else {
-                    self.s1
-                };
-                self.progress = ctx.animate_value_with_time(anim_id, 1.0, self.animation_time);
-                ctx.style_mut(|style| self.set_egui_animation(style, tokens, shadow));
-                if self.progress == 1.0 {
-                    ctx.animate_value_with_time(anim_id, 0.0, 0.0);
-                }
-            } else if let Some(ui) = ui {
-                let shadow = if ui.style_mut().visuals.dark_mode {
-                    self.s2
-                } else {
-                    self.s1
-                };
-                self.progress = ui
-                    .ctx()
-                    .animate_value_with_time(anim_id, 1.0, self.animation_time);
-                self.set_egui_animation(ui.style_mut(), tokens, shadow);
-                if self.progress == 1.0 {
-                    ui.ctx().animate_value_with_time(anim_id, 0.0, 0.0);
-                }
-            }
+            return;
+        }
+        if !self.id.registered {
+            return;
+        }
+        let anim_id = self.id.id;
+        let progress = ctx.animate_value_with_time(anim_id, 1.0, self.animation_time);
+        self.step(progress, tokens);
+        if progress == 1.0 {
+            ctx.animate_value_with_time(anim_id, 0.0, 0.0);
         }
     }
-    fn set_egui_animation(&mut self, style: &mut Style, tokens: ColorTokens, shadow: Color32) {
-        let indices = [[6, 0, 7], [8, 8, 6]];
-
-        self.values_1
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, v)| *v = self.animated_tokens.get_token(indices[0][i]));
-        self.values_2
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, v)| *v = self.animated_tokens.get_token(indices[1][i]));
-
-        let (start_values, end_values) = if self.token_shift {
-            (&self.values_1, &self.values_2)
+    // computes the animated tokens at `progress`, the same with or without a context
+    fn step(&mut self, progress: f32, tokens: &ColorTokens) {
+        self.progress = progress;
+        let (interpolation, easing, stagger) =
+            (self.interpolation, self.easing.clone(), self.stagger);
+        let mix = |step, start, end| {
+            interpolation.mix(start, end, easing.apply(staggered(progress, stagger, step)))
+        };
+        for i in 0..12 {
+            let color = mix(i, self.tokens.get_token(i), tokens.get_token(i));
+            self.animated_tokens.update_schema(i, color);
+        }
+        // text on the accent changes together with the other text
+        self.animated_tokens.on_accent = mix(11, self.tokens.on_accent, tokens.on_accent);
+        for (i, scale) in self.animated_tokens.status.iter_mut().enumerate() {
+            for (step, color) in scale.iter_mut().enumerate() {
+                *color = mix(step, self.tokens.status[i][step], tokens.status[i][step]);
+            }
+        }
+        self.animated_tokens.inverse_color = tokens.inverse_color;
+        // a light/dark switch cross-fades the colors, the mode itself flips halfway
+        self.animated_tokens.dark_mode = if easing.apply(progress) < 0.5 {
+            self.tokens.dark_mode
         } else {
-            (&self.values_2, &self.values_1)
+            tokens.dark_mode
         };
-        self.tokenshifts.iter_mut().enumerate().for_each(|(i, v)| {
-            *v = interpolate_color(start_values[i], end_values[i], self.progress)
-        });
-
-        self.animated_tokens.app_background = interpolate_color(
-            self.tokens.app_background,
-            tokens.app_background,
-            self.progress,
-        );
-        self.animated_tokens.subtle_background = interpolate_color(
-            self.tokens.subtle_background,
-            tokens.subtle_background,
-            self.progress,
-        );
-        self.animated_tokens.ui_element_background = interpolate_color(
-            self.tokens.ui_element_background,
-            tokens.subtle_background,
-            self.progress,
-        );
-        self.animated_tokens.hovered_ui_element_background = interpolate_color(
-            self.tokens.hovered_ui_element_background,
-            tokens.hovered_ui_element_background,
-            self.progress,
-        );
-        self.animated_tokens.active_ui_element_background = interpolate_color(
-            self.tokens.active_ui_element_background,
-            tokens.active_ui_element_background,
-            self.progress,
-        );
-        self.animated_tokens.subtle_borders_and_separators = interpolate_color(
-            self.tokens.subtle_borders_and_separators,
-            tokens.subtle_borders_and_separators,
-            self.progress,
-        );
-        self.animated_tokens.ui_element_border_and_focus_rings = interpolate_color(
-            self.tokens.ui_element_border_and_focus_rings,
-            tokens.ui_element_border_and_focus_rings,
-            self.progress,
-        );
-        self.animated_tokens.hovered_ui_element_border = interpolate_color(
-            self.tokens.hovered_ui_element_border,
-            tokens.hovered_ui_element_border,
-            self.progress,
-        );
-        self.animated_tokens.solid_backgrounds = interpolate_color(
-            self.tokens.solid_backgrounds,
-            tokens.solid_backgrounds,
-            self.progress,
-        );
-        self.animated_tokens.hovered_solid_backgrounds = interpolate_color(
-            self.tokens.hovered_solid_backgrounds,
-            tokens.hovered_solid_backgrounds,
-            self.progress,
-        );
-        self.animated_tokens.low_contrast_text = interpolate_color(
-            self.tokens.low_contrast_text,
-            tokens.low_contrast_text,
-            self.progress,
-        );
-        self.animated_tokens.high_contrast_text = interpolate_color(
-            self.tokens.high_contrast_text,
-            tokens.high_contrast_text,
-            self.progress,
-        );
-        self.animated_tokens.on_accent =
-            interpolate_color(self.tokens.on_accent, tokens.on_accent, self.progress);
-        self.shadow = interpolate_color(self.shadow, shadow, self.progress);
+        self.shadow = mix(0, self.start_shadow, tokens.shadow());
 
+        // reset old values and flag of animate value
+        if progress == 1.0 {
+            self.finish();
+        }
+    }
+    pub(crate) fn set_egui_animation(
+        &self,
+        style: &mut Style,
+        shape: &StyleShape,
+        mapping: &TokenMapping,
+    ) {
         match self.apply_to {
-            ApplyTo::Global | ApplyTo::Local => {
-                let selection = egui::style::Selection {
-                    bg_fill: self.animated_tokens.solid_backgrounds,
-                    stroke: Stroke::new(1.0, self.animated_tokens.on_accent),
-                };
-                let text_cursor = TextCursorStyle {
-                    stroke: Stroke::new(2.0, self.animated_tokens.low_contrast_text),
-                    ..Default::default()
-                };
-                let widgets = egui::style::Widgets {
-                    noninteractive: WidgetVisuals {
-                        weak_bg_fill: self.animated_tokens.subtle_background,
-                        bg_fill: self.animated_tokens.subtle_background,
-                        bg_stroke: Stroke::new(
-                            1.0,
-                            self.animated_tokens.subtle_borders_and_separators,
-                        ), // separators, indentation lines
-                        fg_stroke: Stroke::new(1.0, self.animated_tokens.low_contrast_text), // normal text color
-                        rounding: Rounding::same(2.0),
-                        expansion: 0.0,
-                    },
-                    inactive: WidgetVisuals {
-                        weak_bg_fill: self.animated_tokens.ui_element_background, // button background
-                        bg_fill: self.animated_tokens.ui_element_background, // checkbox background
-                        bg_stroke: Stroke::new(1.0, self.animated_tokens.ui_element_background),
-                        fg_stroke: Stroke::new(1.0, self.animated_tokens.low_contrast_text), // button text
-                        rounding: Rounding::same(2.0),
-                        expansion: 0.0,
-                    },
-                    hovered: WidgetVisuals {
-                        weak_bg_fill: self.animated_tokens.hovered_ui_element_background,
-                        bg_fill: self.animated_tokens.hovered_ui_element_background,
-                        bg_stroke: Stroke::new(1.0, self.animated_tokens.hovered_ui_element_border), // e.g. hover over window edge or button
-                        fg_stroke: Stroke::new(1.5, self.animated_tokens.high_contrast_text),
-                        rounding: Rounding::same(3.0),
-                        expansion: 1.0,
-                    },
-                    active: WidgetVisuals {
-                        weak_bg_fill: self.animated_tokens.active_ui_element_background,
-                        bg_fill: self.animated_tokens.active_ui_element_background,
-                        bg_stroke: Stroke::new(
-                            1.0,
-                            self.animated_tokens.ui_element_border_and_focus_rings,
-                        ),
-                        fg_stroke: Stroke::new(2.0, self.animated_tokens.high_contrast_text),
-                        rounding: Rounding::same(2.0),
-                        expansion: 1.0,
-                    },
-                    open: WidgetVisuals {
-                        weak_bg_fill: self.animated_tokens.active_ui_element_background,
-                        bg_fill: self.animated_tokens.active_ui_element_background,
-                        bg_stroke: Stroke::new(
-                            1.0,
-                            self.animated_tokens.ui_element_border_and_focus_rings,
-                        ),
-                        fg_stroke: Stroke::new(1.0, self.animated_tokens.high_contrast_text),
-                        rounding: Rounding::same(2.0),
-                        expansion: 0.0,
-                    },
-                };
-                style.visuals.selection = selection;
-                style.visuals.widgets = widgets;
-                style.visuals.text_cursor = text_cursor;
-                style.visuals.extreme_bg_color = self.animated_tokens.app_background; // e.g. TextEdit background
-                style.visuals.faint_bg_color = self.animated_tokens.app_background; // striped grid is originally from_additive_luminance(5)
-                style.visuals.code_bg_color = self.animated_tokens.ui_element_background;
-                style.visuals.window_fill = self.animated_tokens.subtle_background;
-                style.visuals.window_stroke =
-                    Stroke::new(1.0, self.animated_tokens.subtle_borders_and_separators);
-                style.visuals.panel_fill = self.animated_tokens.subtle_background;
-                style.visuals.hyperlink_color = self.animated_tokens.hovered_solid_backgrounds;
+            ApplyTo::Global | ApplyTo::Local | ApplyTo::Scope => {
+                style.visuals = self
+                    .animated_tokens
+                    .to_visuals(&style.visuals, shape, mapping);
                 style.visuals.window_shadow.color = self.shadow;
-
-                // reset old values and flag of animate value
-                if self.progress == 1.0 {
-                    self.tokens = self.animated_tokens;
-                    self.animation_done = true;
-                    self.animation_in_progress = false;
-                    self.token_shift = !self.token_shift;
-                }
-            }
-            ApplyTo::ExtraScale => {
-                if self.progress == 1.0 {
-                    self.tokens = self.animated_tokens;
-                    self.animation_done = true;
-                    self.animation_in_progress = false;
-                    self.token_shift = !self.token_shift;
-                }
+                style.visuals.popup_shadow.color = self.shadow;
             }
+            ApplyTo::ExtraScale => {}
         }
     }
-    fn apply_local_ui(&self, style: &mut egui::style::Style) {
-        let selection = egui::style::Selection {
-            bg_fill: self.animated_tokens.solid_backgrounds,
-            stroke: Stroke::new(1.0, self.animated_tokens.on_accent),
-        };
-        let text_cursor = TextCursorStyle {
-            stroke: Stroke::new(2.0, self.animated_tokens.low_contrast_text),
-            ..Default::default()
-        };
-        let widgets = egui::style::Widgets {
-            noninteractive: WidgetVisuals {
-                weak_bg_fill: self.animated_tokens.subtle_background,
-                bg_fill: self.animated_tokens.subtle_background,
-                bg_stroke: Stroke::new(1.0, self.animated_tokens.subtle_borders_and_separators), // separators, indentation lines
-                fg_stroke: Stroke::new(1.0, self.animated_tokens.low_contrast_text), // normal text color
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
-            },
-            inactive: WidgetVisuals {
-                weak_bg_fill: self.animated_tokens.ui_element_background, // button background
-                bg_fill: self.animated_tokens.ui_element_background,      // checkbox background
-                bg_stroke: Stroke::new(1.0, self.animated_tokens.ui_element_background),
-                fg_stroke: Stroke::new(1.0, self.animated_tokens.low_contrast_text), // button text
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
-            },
-            hovered: WidgetVisuals {
-                weak_bg_fill: self.animated_tokens.hovered_ui_element_background,
-                bg_fill: self.animated_tokens.hovered_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.animated_tokens.hovered_ui_element_border), // e.g. hover over window edge or button
-                fg_stroke: Stroke::new(1.5, self.animated_tokens.high_contrast_text),
-                rounding: Rounding::same(3.0),
-                expansion: 1.0,
-            },
-            active: WidgetVisuals {
-                weak_bg_fill: self.animated_tokens.active_ui_element_background,
-                bg_fill: self.animated_tokens.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.animated_tokens.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(2.0, self.animated_tokens.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 1.0,
-            },
-            open: WidgetVisuals {
-                weak_bg_fill: self.animated_tokens.active_ui_element_background,
-                bg_fill: self.animated_tokens.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.animated_tokens.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(1.0, self.animated_tokens.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
-            },
-        };
-        style.visuals.selection = selection;
-        style.visuals.widgets = widgets;
-        style.visuals.text_cursor = text_cursor;
-        style.visuals.extreme_bg_color = self.animated_tokens.app_background; // e.g. TextEdit background
-        style.visuals.faint_bg_color = self.animated_tokens.app_background; // striped grid is originally from_additive_luminance(5)
-        style.visuals.code_bg_color = self.animated_tokens.ui_element_background;
-        style.visuals.window_fill = self.animated_tokens.subtle_background;
-        style.visuals.window_stroke =
-            Stroke::new(1.0, self.animated_tokens.subtle_borders_and_separators);
-        style.visuals.panel_fill = self.animated_tokens.subtle_background;
-        style.visuals.hyperlink_color = self.animated_tokens.hovered_solid_backgrounds;
-        style.visuals.window_shadow.color = self.shadow;
+    const fn finish(&mut self) {
+        self.tokens = self.animated_tokens;
+        self.animation_done = true;
+        self.animation_in_progress = false;
+        self.finished = true;
     }
 }
@@ -78,6 +78,69 @@ pub fn from_degrees(hue: f32) -> f32 {
     (hue / 360.).clamp(0., 1.)
 }
 
+/// Converts an Oklch color (hue in degrees) to gamma encoded rgb, clamped to the srgb gamut.
+pub fn oklch_to_u8(lightness: f32, chroma: f32, hue: f32) -> [u8; 3] {
+    let (sin, cos) = hue.to_radians().sin_cos();
+    Oklab {
+        l: lightness,
+        a: chroma * cos,
+        b: chroma * sin,
+    }
+    .to_linear_srgb()
+    .from_linear()
+}
+
+/// Mixes two gamma encoded rgb colors in linear srgb.
+pub fn mix_linear_srgb(start: [u8; 3], end: [u8; 3], t: f32) -> [u8; 3] {
+    let (start, end) = (LinSrgb::into_linear(start), LinSrgb::into_linear(end));
+    LinSrgb {
+        red: egui::lerp(start.red..=end.red, t),
+        green: egui::lerp(start.green..=end.green, t),
+        blue: egui::lerp(start.blue..=end.blue, t),
+    }
+    .from_linear()
+}
+
+/// Mixes two gamma encoded rgb colors in Oklab.
+pub fn mix_oklab(start: [u8; 3], end: [u8; 3], t: f32) -> [u8; 3] {
+    let start = linear_srgb_to_oklab(LinSrgb::into_linear(start));
+    let end = linear_srgb_to_oklab(LinSrgb::into_linear(end));
+    Oklab {
+        l: egui::lerp(start.l..=end.l, t),
+        a: egui::lerp(start.a..=end.a, t),
+        b: egui::lerp(start.b..=end.b, t),
+    }
+    .to_linear_srgb()
+    .from_linear()
+}
+
+/// Mixes two gamma encoded rgb colors in Oklch, along the shortest path around the hue circle.
+pub fn mix_oklch(start: [u8; 3], end: [u8; 3], t: f32) -> [u8; 3] {
+    // an achromatic color has no hue, it takes the hue of the other color
+    const ACHROMATIC: f32 = 1e-4;
+    let start = linear_srgb_to_oklab(LinSrgb::into_linear(start));
+    let end = linear_srgb_to_oklab(LinSrgb::into_linear(end));
+    let (c1, c2) = (start.a.hypot(start.b), end.a.hypot(end.b));
+    let mut h1 = start.b.atan2(start.a);
+    let mut h2 = end.b.atan2(end.a);
+    if c1 < ACHROMATIC {
+        h1 = h2;
+    }
+    if c2 < ACHROMATIC {
+        h2 = h1;
+    }
+    let dh = (h2 - h1 + PI).rem_euclid(2. * PI) - PI;
+    let c = egui::lerp(c1..=c2, t);
+    let (sin, cos) = (h1 + dh * t).sin_cos();
+    Oklab {
+        l: egui::lerp(start.l..=end.l, t),
+        a: c * cos,
+        b: c * sin,
+    }
+    .to_linear_srgb()
+    .from_linear()
+}
+
 #[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
 struct Oklab {
     l: f32,
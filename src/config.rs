@@ -0,0 +1,140 @@
+use crate::{
+    easing::Easing,
+    interpolation::Interpolation,
+    mapping::TokenMapping,
+    shape::StyleShape,
+    tokens::{ColorTokens, ThemeColor},
+    ApplyTo, Colorix, Theme,
+};
+use egui::{epaint::Hsva, Context, Id};
+use std::collections::BTreeMap;
+
+/// A snapshot of the state of a [`Colorix`] that can be used to restore it.
+///
+/// With the `serde` feature it can be persisted, e.g. in eframe storage.
+///
+/// Not restored are:
+/// * keyframe tracks, add them again with [`Colorix::add_track`]
+/// * a running animation, the restored Colorix shows the colors it was animating to
+/// * a `Custom` easing when the config is serialized, it is read back as `Linear`
+/// * the animation id, unless it was set with [`Colorix::animation_id`]
+/// * the text pasted in [`Colorix::ui_combo_12`]
+///
+/// # Examples
+///
+/// ```ignore
+/// // save
+/// eframe::set_value(storage, "colorix", &app.colorix.to_config());
+///
+/// // restore
+/// let colorix = eframe::get_value(storage, "colorix")
+///     .map(|config| Colorix::from_config(ctx, &config))
+///     .unwrap_or_else(|| Colorix::global(ctx, utils::EGUI_THEME));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThemeConfig {
+    pub theme: Theme,
+    /// the light and dark variant of the theme, if they differ
+    pub mode_themes: Option<[Theme; 2]>,
+    pub dark_mode: bool,
+    pub apply_to: ApplyTo,
+    pub animated: bool,
+    pub animation_time: f32,
+    pub interpolation: Interpolation,
+    /// a `Custom` easing is serialized as `Linear`
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_easing"))]
+    pub easing: Easing,
+    pub stagger: f32,
+    /// the id set with [`Colorix::animation_id`]
+    pub animation_id: Option<Id>,
+    /// color of the custom color picker
+    pub custom: [u8; 3],
+    /// the tokens as they were resolved when the snapshot was taken
+    pub tokens: ColorTokens,
+    pub shape: StyleShape,
+    pub mapping: TokenMapping,
+    /// the scales registered with [`Colorix::register_scale`]
+    pub named_scales: BTreeMap<String, ThemeColor>,
+}
+
+impl Colorix {
+    /// Restores a Colorix from a [`ThemeConfig`], applying to what it applied to when it was saved.
+    ///
+    /// The stored tokens are applied as they are, so the ui looks exactly as when the config was saved.
+    /// Only a global Colorix changes the style of `ctx`, a local one styles the ui on the next
+    /// `update_locally` and a scoped one on the next `scope`.
+    #[must_use]
+    pub fn from_config(ctx: &Context, config: &ThemeConfig) -> Self {
+        let mut colorix = Self {
+            theme: config.theme,
+            mode_themes: config.mode_themes,
+            ..Default::default()
+        };
+        colorix.apply_to = config.apply_to;
+        colorix.set_colorix_mode(config.dark_mode);
+        colorix.get_theme_index();
+        for (name, color) in &config.named_scales {
+            colorix.register_scale(name.clone(), *color);
+        }
+        if let Some(id) = config.animation_id {
+            colorix.animator.set_id(id);
+        }
+        colorix.scales.custom = Hsva::from_srgb(config.custom);
+        colorix.tokens = config.tokens;
+        colorix.shape = config.shape;
+        colorix.mapping = config.mapping;
+        if config.animated {
+            colorix = colorix
+                .animated()
+                .set_time(config.animation_time)
+                .interpolation(config.interpolation)
+                .easing(config.easing.clone())
+                .stagger(config.stagger);
+        }
+        if colorix.apply_to == ApplyTo::Global {
+            ctx.style_mut(|style| style.visuals.dark_mode = config.dark_mode);
+            colorix.set_ctx_visuals(ctx);
+        }
+        colorix
+    }
+
+    /// Takes a snapshot of the current state.
+    #[must_use]
+    pub fn to_config(&self) -> ThemeConfig {
+        ThemeConfig {
+            theme: self.theme,
+            mode_themes: self.mode_themes,
+            dark_mode: self.dark_mode(),
+            apply_to: self.apply_to,
+            animated: self.animated,
+            animation_time: self.animator.animation_time(),
+            interpolation: self.animator.interpolation(),
+            easing: self.animator.easing().clone(),
+            stagger: self.animator.stagger(),
+            animation_id: self.animator.fixed_id(),
+            custom: self.scales.custom(),
+            tokens: self.tokens,
+            shape: self.shape,
+            mapping: self.mapping,
+            named_scales: self
+                .named_scales
+                .iter()
+                .map(|(name, (color, _))| (name.clone(), *color))
+                .collect(),
+        }
+    }
+}
+
+// closures can't be serialized
+#[cfg(feature = "serde")]
+fn serialize_easing<S: serde::Serializer>(
+    easing: &Easing,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::Serialize;
+    match easing {
+        Easing::Custom(_) => Easing::Linear.serialize(serializer),
+        easing => easing.serialize(serializer),
+    }
+}
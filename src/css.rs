@@ -0,0 +1,56 @@
+use crate::{tokens::ColorTokens, utils::LABELS, Colorix};
+use egui::{ecolor::HexColor, Color32};
+use std::fmt::Write as _;
+
+pub fn css_color(color: Color32) -> String {
+    if color.is_opaque() {
+        HexColor::Hex6(color).to_string()
+    } else {
+        HexColor::Hex8(color).to_string()
+    }
+}
+
+impl ColorTokens {
+    /// The tokens as CSS custom properties, one declaration per line.
+    /// The names follow the labels of the scale, e.g. `--app-background` up to `--high-contrast-text`, and `--on-accent`.
+    #[must_use]
+    pub fn css_variables(&self, indent: &str) -> String {
+        let mut css = String::new();
+        for (i, label) in LABELS.iter().enumerate() {
+            let _ = writeln!(
+                css,
+                "{indent}--{}: {};",
+                label.replace(' ', "-"),
+                css_color(self.get_token(i))
+            );
+        }
+        let _ = writeln!(css, "{indent}--on-accent: {};", css_color(self.on_accent));
+        css
+    }
+}
+
+impl Colorix {
+    /// CSS custom properties of the light and the dark scale of the current theme.
+    ///
+    /// With `media_query` the dark scale is wrapped in a `@media (prefers-color-scheme: dark)` block,
+    /// otherwise the scales are selected with a `data-theme="light"` or `data-theme="dark"` attribute.
+    #[must_use]
+    pub fn css_variables(&self, media_query: bool) -> String {
+        let [light, dark] = self.mode_themes.unwrap_or([self.theme; 2]);
+        let light = ColorTokens::from_theme(&light, false);
+        let dark = ColorTokens::from_theme(&dark, true);
+        if media_query {
+            format!(
+                ":root {{\n{}}}\n\n@media (prefers-color-scheme: dark) {{\n  :root {{\n{}  }}\n}}\n",
+                light.css_variables("  "),
+                dark.css_variables("    ")
+            )
+        } else {
+            format!(
+                ":root, [data-theme=\"light\"] {{\n{}}}\n\n[data-theme=\"dark\"] {{\n{}}}\n",
+                light.css_variables("  "),
+                dark.css_variables("  ")
+            )
+        }
+    }
+}
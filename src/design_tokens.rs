@@ -0,0 +1,267 @@
+//! Import and export in the [W3C Design Tokens](https://tr.designtokens.org/format/) JSON format.
+//!
+//! The file contains a `light` and a `dark` group with the 12 tokens and `on-accent`,
+//! and a `scales` group with the 12 steps of every color of the theme in both modes:
+//!
+//! ```json
+//! {
+//!   "light": {
+//!     "app-background": { "$type": "color", "$value": "#fcfcfc" },
+//!     "on-accent": { "$type": "color", "$value": "#ffffff" }
+//!   },
+//!   "dark": { },
+//!   "scales": {
+//!     "Gold": {
+//!       "light": { "1": { "$type": "color", "$value": "#fdfdfc" } },
+//!       "dark": { }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Imported tokens are applied with [`Colorix::from_design_tokens`], which needs the `scales` group.
+use crate::{
+    css::css_color,
+    scales::generate_scale,
+    tokens::{ColorTokens, ThemeColor},
+    utils::LABELS,
+    Colorix, Theme,
+};
+use egui::{Color32, Context};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Errors that can occur when reading design tokens.
+#[derive(Debug)]
+pub enum DesignTokensError {
+    Json(serde_json::Error),
+    /// A token is missing, e.g. `dark.app-background`.
+    MissingToken(String),
+    /// A token doesn't have a valid hex color as `$value`.
+    InvalidColor {
+        token: String,
+        value: String,
+    },
+    /// No scale of the `scales` group contains a token, so the theme can't be restored.
+    MissingScale(String),
+}
+
+impl std::fmt::Display for DesignTokensError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid json: {err}"),
+            Self::MissingToken(token) => write!(f, "missing token `{token}`"),
+            Self::InvalidColor { token, value } => {
+                write!(f, "invalid color `{value}` for `{token}`")
+            }
+            Self::MissingScale(token) => write!(f, "no scale contains `{token}`"),
+        }
+    }
+}
+
+impl std::error::Error for DesignTokensError {}
+
+/// The tokens of both modes and the scales they are computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignTokens {
+    pub light: ColorTokens,
+    pub dark: ColorTokens,
+    /// the light and dark scale of every color, keyed by preset name or `#rrggbb`
+    pub scales: BTreeMap<String, [[Color32; 12]; 2]>,
+}
+
+fn token_name(i: usize) -> String {
+    LABELS[i].replace(' ', "-")
+}
+
+fn write_token(color: Color32) -> Value {
+    json!({ "$type": "color", "$value": css_color(color) })
+}
+
+fn read_token(group: &Value, path: &str, name: &str) -> Result<Color32, DesignTokensError> {
+    let token = format!("{path}.{name}");
+    let value = group
+        .get(name)
+        .and_then(|t| t.get("$value"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| DesignTokensError::MissingToken(token.clone()))?;
+    Color32::from_hex(value).map_err(|_| DesignTokensError::InvalidColor {
+        token,
+        value: value.to_string(),
+    })
+}
+
+fn write_tokens(tokens: &ColorTokens) -> Value {
+    let mut group: Map<String, Value> = (0..12)
+        .map(|i| (token_name(i), write_token(tokens.get_token(i))))
+        .collect();
+    group.insert("on-accent".to_string(), write_token(tokens.on_accent));
+    Value::Object(group)
+}
+
+fn read_tokens(root: &Value, mode: &str) -> Result<ColorTokens, DesignTokensError> {
+    let group = root
+        .get(mode)
+        .ok_or_else(|| DesignTokensError::MissingToken(mode.to_string()))?;
+    let mut tokens = ColorTokens {
+        dark_mode: mode == "dark",
+        ..Default::default()
+    };
+    for i in 0..12 {
+        tokens.update_schema(i, read_token(group, mode, &token_name(i))?);
+    }
+    tokens.on_accent = read_token(group, mode, "on-accent")?;
+    tokens.inverse_color = tokens.on_accent != Color32::WHITE;
+    tokens.update_status();
+    Ok(tokens)
+}
+
+fn write_scale(scale: &[Color32; 12]) -> Value {
+    Value::Object(
+        scale
+            .iter()
+            .enumerate()
+            .map(|(i, color)| ((i + 1).to_string(), write_token(*color)))
+            .collect(),
+    )
+}
+
+fn read_scale(group: &Value, path: &str) -> Result<[Color32; 12], DesignTokensError> {
+    let mut scale = [Color32::TRANSPARENT; 12];
+    for (i, color) in scale.iter_mut().enumerate() {
+        *color = read_token(group, path, &(i + 1).to_string())?;
+    }
+    Ok(scale)
+}
+
+impl DesignTokens {
+    /// Computes the tokens and scales of a theme.
+    #[must_use]
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self::from_mode_themes(theme, theme)
+    }
+
+    fn from_mode_themes(light: &Theme, dark: &Theme) -> Self {
+        let scales = light
+            .iter()
+            .chain(dark)
+            .map(|color| {
+                (
+                    color.to_string(),
+                    [
+                        generate_scale(color.rgb(), false),
+                        generate_scale(color.rgb(), true),
+                    ],
+                )
+            })
+            .collect();
+        Self {
+            light: ColorTokens::from_theme(light, false),
+            dark: ColorTokens::from_theme(dark, true),
+            scales,
+        }
+    }
+
+    /// The theme of a mode, restored from the scales that contain the tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token isn't a step of any of the scales.
+    pub fn theme(&self, dark_mode: bool) -> Result<Theme, DesignTokensError> {
+        let (tokens, mode) = if dark_mode {
+            (&self.dark, "dark")
+        } else {
+            (&self.light, "light")
+        };
+        let mut theme = Theme::default();
+        for (i, color) in theme.iter_mut().enumerate() {
+            *color = self
+                .scales
+                .iter()
+                .filter(|(_, scales)| scales[usize::from(dark_mode)][i] == tokens.get_token(i))
+                .find_map(|(name, _)| name.parse::<ThemeColor>().ok())
+                .ok_or_else(|| {
+                    DesignTokensError::MissingScale(format!("{mode}.{}", token_name(i)))
+                })?;
+        }
+        Ok(theme)
+    }
+
+    /// Writes the design tokens as pretty printed JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let scales: Map<String, Value> = self
+            .scales
+            .iter()
+            .map(|(name, [light, dark])| {
+                (
+                    name.clone(),
+                    json!({ "light": write_scale(light), "dark": write_scale(dark) }),
+                )
+            })
+            .collect();
+        let root = json!({
+            "light": write_tokens(&self.light),
+            "dark": write_tokens(&self.dark),
+            "scales": scales,
+        });
+        format!("{root:#}")
+    }
+
+    /// Reads design tokens from JSON. The `scales` group is optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, or a token is missing or isn't a hex color.
+    pub fn from_json(s: &str) -> Result<Self, DesignTokensError> {
+        let root: Value = serde_json::from_str(s).map_err(DesignTokensError::Json)?;
+        let mut scales = BTreeMap::new();
+        if let Some(Value::Object(groups)) = root.get("scales") {
+            for (name, group) in groups {
+                let read_mode = |mode: &str| {
+                    let path = format!("scales.{name}.{mode}");
+                    group
+                        .get(mode)
+                        .ok_or_else(|| DesignTokensError::MissingToken(path.clone()))
+                        .and_then(|group| read_scale(group, &path))
+                };
+                scales.insert(name.clone(), [read_mode("light")?, read_mode("dark")?]);
+            }
+        }
+        Ok(Self {
+            light: read_tokens(&root, "light")?,
+            dark: read_tokens(&root, "dark")?,
+            scales,
+        })
+    }
+}
+
+impl Colorix {
+    /// Restores a global Colorix from design tokens, e.g. read with [`DesignTokens::from_json`].
+    ///
+    /// The theme of both modes is restored from the `scales` group,
+    /// so switching between light and dark mode keeps working.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token isn't a step of any of the scales.
+    pub fn from_design_tokens(
+        ctx: &Context,
+        tokens: &DesignTokens,
+    ) -> Result<Self, DesignTokensError> {
+        let themes = [tokens.theme(false)?, tokens.theme(true)?];
+        let dark_mode = ctx.style().visuals.dark_mode;
+        let mut colorix = Self::global(ctx, themes[usize::from(dark_mode)]);
+        if themes[0] != themes[1] {
+            colorix.mode_themes = Some(themes);
+        }
+        Ok(colorix)
+    }
+
+    /// The design tokens of the current theme in both modes.
+    #[must_use]
+    pub fn design_tokens(&self) -> DesignTokens {
+        let [light, dark] = self.mode_themes.unwrap_or([self.theme; 2]);
+        DesignTokens::from_mode_themes(&light, &dark)
+    }
+}
@@ -0,0 +1,89 @@
+#![allow(clippy::float_cmp)]
+#![allow(clippy::suboptimal_flops)]
+
+use std::{f32::consts::PI, fmt, sync::Arc};
+
+/// The curve along which an animated transition progresses.
+///
+/// Every curve starts at 0.0 and ends at 1.0, so the animation always ends on the new colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::easing::Easing;
+/// assert_eq!(Easing::Cubic.apply(0.5), 0.5);
+/// assert!(Easing::Cubic.apply(0.25) < Easing::Linear.apply(0.25));
+///
+/// let exponent = 3;
+/// let polynomial = Easing::custom(move |t| t.powi(exponent));
+/// assert_eq!(polynomial.apply(0.5), 0.125);
+/// ```
+///
+/// With the `serde` feature every curve but `Custom` can be persisted.
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Sine shaped, slow at the start and the end
+    EaseInOut,
+    /// Cubic ease in and out, more pronounced than `EaseInOut`
+    Cubic,
+    /// Overshoots and settles like a damped spring.
+    /// Colors can't overshoot, so they hold the new color for a moment.
+    Spring,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => write!(f, "Linear"),
+            Self::EaseInOut => write!(f, "EaseInOut"),
+            Self::Cubic => write!(f, "Cubic"),
+            Self::Spring => write!(f, "Spring"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+// custom curves are equal if they are the same closure
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+}
+
+impl Easing {
+    /// A curve of your own, the closure may capture its parameters.
+    #[must_use]
+    pub fn custom(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Maps the linear progress `t` (clamped to 0.0..=1.0) onto the curve.
+    #[must_use]
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        if t == 0. || t == 1. {
+            return t;
+        }
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut => (1. - (PI * t).cos()) / 2.,
+            Self::Cubic => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (2. - 2. * t).powi(3) / 2.
+                }
+            }
+            Self::Spring => 1. - (-6. * t).exp() * (3. * PI * t).cos(),
+            Self::Custom(f) => f(t),
+        }
+    }
+}
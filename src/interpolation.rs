@@ -0,0 +1,65 @@
+#![allow(clippy::many_single_char_names)]
+
+use crate::color_space::{mix_linear_srgb, mix_oklab, mix_oklch};
+use egui::Color32;
+
+/// The color space in which animated colors are mixed.
+///
+/// # Examples
+///
+/// ```
+/// use egui::Color32;
+/// use egui_colors::interpolation::Interpolation;
+/// let orange = Color32::from_rgb(255, 165, 0);
+/// let srgb = Interpolation::Srgb.mix(Color32::BLUE, orange, 0.5);
+/// let oklch = Interpolation::Oklch.mix(Color32::BLUE, orange, 0.5);
+///
+/// // the srgb midpoint is grayish, the oklch midpoint keeps its chroma
+/// let spread = |c: Color32| c.r().max(c.g()).max(c.b()) - c.r().min(c.g()).min(c.b());
+/// assert!(spread(oklch) > spread(srgb));
+/// assert_eq!(Interpolation::Oklch.mix(Color32::BLUE, orange, 1.0), orange);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Gamma encoded srgb. Cheap, but the midpoints of distant hues turn muddy.
+    #[default]
+    Srgb,
+    LinearSrgb,
+    /// Perceptually uniform, keeps the lightness steady
+    Oklab,
+    /// Like `Oklab`, but keeps the chroma and goes the shortest way around the hue circle
+    Oklch,
+}
+
+impl Interpolation {
+    /// Mixes two colors, `t` is clamped between 0.0 (`start`) and 1.0 (`end`).
+    #[must_use]
+    pub fn mix(self, start: Color32, end: Color32, t: f32) -> Color32 {
+        if t <= 0. {
+            return start;
+        }
+        if t >= 1. {
+            return end;
+        }
+        let mix = match self {
+            Self::Srgb => return lerp_premultiplied(start, end, t),
+            Self::LinearSrgb => mix_linear_srgb,
+            Self::Oklab => mix_oklab,
+            Self::Oklch => mix_oklch,
+        };
+        let [r1, g1, b1, a1] = start.to_srgba_unmultiplied();
+        let [r2, g2, b2, a2] = end.to_srgba_unmultiplied();
+        let [r, g, b] = mix([r1, g1, b1], [r2, g2, b2], t);
+        let a = egui::lerp(f32::from(a1)..=f32::from(a2), t) as u8;
+        Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
+
+fn lerp_premultiplied(start: Color32, end: Color32, t: f32) -> Color32 {
+    let r = egui::lerp(f32::from(start.r())..=f32::from(end.r()), t) as u8;
+    let g = egui::lerp(f32::from(start.g())..=f32::from(end.g()), t) as u8;
+    let b = egui::lerp(f32::from(start.b())..=f32::from(end.b()), t) as u8;
+    let a = egui::lerp(f32::from(start.a())..=f32::from(end.a()), t) as u8;
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
@@ -0,0 +1,136 @@
+use crate::{easing::Easing, interpolation::Interpolation, tokens::ColorTokens, tokens::Token};
+use egui::Color32;
+
+/// How a track continues after its last keyframe
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Repeat {
+    /// Holds the last keyframe
+    #[default]
+    Once,
+    /// Jumps back to the first keyframe
+    Loop,
+    /// Runs back and forth
+    PingPong,
+}
+
+/// A color animation along keyframes that reference tokens, e.g. for pulsing highlights.
+///
+/// The keyframes are resolved against the current tokens, so a track follows theme changes.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{keyframes::{ColorTrack, Repeat}, tokens::{ColorTokens, Token}, utils::WARM};
+/// let pulse = ColorTrack::between(Token::SolidBackgrounds, Token::HoveredSolidBackgrounds, 0.8)
+///     .repeat(Repeat::PingPong);
+///
+/// let tokens = ColorTokens::from_theme(&WARM, true);
+/// assert_eq!(pulse.sample(&tokens, 0.0), tokens.solid_backgrounds());
+/// assert_eq!(pulse.sample(&tokens, 0.8), tokens.hovered_solid_backgrounds());
+/// assert_eq!(pulse.sample(&tokens, 1.6), tokens.solid_backgrounds());
+/// ```
+///
+/// A track is played by a `Colorix`:
+///
+/// ```ignore
+/// app.colorix.add_track("pulse", pulse);
+/// app.colorix.play_track(ctx, "pulse");
+/// // every frame
+/// let color = app.colorix.track_color(ctx, "pulse");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColorTrack {
+    // sorted by the position in the cycle, from 0.0 to 1.0
+    keyframes: Vec<(f32, Token)>,
+    duration: f32,
+    repeat: Repeat,
+    easing: Easing,
+    interpolation: Interpolation,
+}
+
+impl ColorTrack {
+    /// An empty track of which a cycle lasts `duration` seconds.
+    #[must_use]
+    pub const fn new(duration: f32) -> Self {
+        Self {
+            keyframes: vec![],
+            duration,
+            repeat: Repeat::Once,
+            easing: Easing::Linear,
+            interpolation: Interpolation::Srgb,
+        }
+    }
+    /// A track from one token to another.
+    #[must_use]
+    pub fn between(from: Token, to: Token, duration: f32) -> Self {
+        Self::new(duration).keyframe(0.0, from).keyframe(1.0, to)
+    }
+    /// Adds a keyframe at a position in the cycle, from 0.0 to 1.0.
+    #[must_use]
+    pub fn keyframe(mut self, at: f32, token: Token) -> Self {
+        let at = at.clamp(0.0, 1.0);
+        let i = self.keyframes.partition_point(|(other, _)| *other <= at);
+        self.keyframes.insert(i, (at, token));
+        self
+    }
+    #[must_use]
+    pub const fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+    /// The curve along which a cycle progresses
+    #[must_use]
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+    #[must_use]
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    #[must_use]
+    pub const fn duration(&self) -> f32 {
+        self.duration
+    }
+    /// Whether a track that runs once has reached its last keyframe.
+    #[must_use]
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        self.repeat == Repeat::Once && elapsed >= self.duration
+    }
+
+    /// The color `elapsed` seconds after the track started.
+    /// An empty track is transparent.
+    #[must_use]
+    pub fn sample(&self, tokens: &ColorTokens, elapsed: f32) -> Color32 {
+        let Some(&(mut prev_at, mut prev)) = self.keyframes.first() else {
+            return Color32::TRANSPARENT;
+        };
+        let cycle = if self.duration > 0.0 {
+            elapsed.max(0.0) / self.duration
+        } else {
+            1.0
+        };
+        let t = match self.repeat {
+            Repeat::Once => cycle.min(1.0),
+            Repeat::Loop => cycle.fract(),
+            Repeat::PingPong => 1.0 - (cycle.rem_euclid(2.0) - 1.0).abs(),
+        };
+        let t = self.easing.apply(t);
+        for &(at, token) in &self.keyframes {
+            if t <= at {
+                let segment = if at > prev_at {
+                    (t - prev_at) / (at - prev_at)
+                } else {
+                    1.0
+                };
+                return self
+                    .interpolation
+                    .mix(tokens.get(prev), tokens.get(token), segment);
+            }
+            (prev_at, prev) = (at, token);
+        }
+        tokens.get(prev)
+    }
+}
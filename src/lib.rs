@@ -9,27 +9,62 @@
 //!
 //!
 
-pub(crate) mod animator;
+/// Transitions between two sets of tokens
+pub mod animator;
 pub(crate) mod apca;
 pub(crate) mod color_space;
-pub(crate) mod scales;
+pub mod config;
+pub(crate) mod css;
+#[cfg(feature = "json")]
+pub mod design_tokens;
+/// Easing curves for animated transitions
+pub mod easing;
+/// Color spaces for animated transitions
+pub mod interpolation;
+/// Keyframe color tracks for highlights and attention effects
+pub mod keyframes;
+/// Which token colors which part of the ui
+pub mod mapping;
+/// Parsing of colors and themes from text
+pub mod parse;
+/// Scale generation without an egui `Context`
+pub mod scales;
+pub(crate) mod scope;
+/// Rounding and stroke widths of the widgets
+pub mod shape;
+#[cfg(any(feature = "toml", feature = "json"))]
+pub mod theme_file;
 pub mod tokens;
 /// Some predefined themes
 pub mod utils;
 
 use animator::ColorAnimator;
-use egui::{Context, Ui};
-use scales::Scales;
+use easing::Easing;
+use egui::{Color32, Context, Ui};
+use interpolation::Interpolation;
+use keyframes::ColorTrack;
+use mapping::TokenMapping;
+use scales::{generate_scale, Scales};
+use scope::ScopeStyle;
+use shape::StyleShape;
+use std::collections::BTreeMap;
 use tokens::{ColorTokens, ThemeColor};
-use utils::{LABELS, THEMES, THEME_NAMES};
+use utils::{LABELS, PRESETS, THEMES, THEME_NAMES};
 
 /// A set of colors that are used together to set a visual feel for the ui
 pub type Theme = [ThemeColor; 12];
 
+/// What a [`Colorix`] styles, set by the constructor it was created with.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ApplyTo {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApplyTo {
+    /// the style of the `Context`, see [`Colorix::global`]
     Global,
+    /// the style of a `Ui`, see [`Colorix::local`]
     Local,
+    /// the ui inside [`Colorix::scope`]
+    Scope,
+    /// nothing, see [`Colorix::extra_scale`]
     #[default]
     ExtraScale,
 }
@@ -65,10 +100,22 @@ pub(crate) enum ApplyTo {
 pub struct Colorix {
     pub tokens: ColorTokens,
     pub(crate) theme: Theme,
+    // light and dark variant of the theme, if they differ
+    mode_themes: Option<[Theme; 2]>,
     theme_index: usize,
+    // text pasted in `ui_combo_12` and the error of the last import
+    import_text: String,
+    import_error: Option<String>,
     pub(crate) scales: Scales,
     animated: bool,
     pub animator: ColorAnimator,
+    // keyframe tracks and the time they started playing
+    tracks: BTreeMap<String, (ColorTrack, Option<f64>)>,
+    // scales registered by name and their colors in the current mode
+    named_scales: BTreeMap<String, (ThemeColor, [Color32; 12])>,
+    scope_style: Option<ScopeStyle>,
+    shape: StyleShape,
+    mapping: TokenMapping,
     pub(crate) apply_to: ApplyTo,
 }
 
@@ -87,7 +134,8 @@ impl Colorix {
         colorix
     }
     /// Initialize a Colorix instance that applies to local ui.
-    /// It needs a `update_locally(ui)` to work.
+    /// It needs a `update_locally(ui)` every frame to work.
+    /// See [`Self::scoped`] for a theme that applies to a child ui with an id of its own.
     pub fn local(ui: &mut Ui, theme: Theme) -> Self {
         let mut colorix = Self {
             theme,
@@ -101,6 +149,7 @@ impl Colorix {
         colorix
     }
     /// Initialize a colorix to provide extra scale. It doesn't apply to any ui.
+    /// Extra colors that only need a scale can also be registered by name with [`Self::register_scale`].
     #[must_use]
     pub fn extra_scale(ctx: &Context, theme: Theme) -> Self {
         let mut colorix = Self {
@@ -130,31 +179,189 @@ impl Colorix {
         self
     }
     #[must_use]
-    pub fn set_time(mut self, new_time: f32) -> Self {
+    pub const fn set_time(mut self, new_time: f32) -> Self {
         if self.animated {
             self.animator.set_time(new_time);
         }
         self
     }
+    /// Sets the color space in which the animation mixes the colors, `Srgb` by default.
+    #[must_use]
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        if self.animated {
+            self.animator.set_interpolation(interpolation);
+        }
+        self
+    }
+    /// Sets the id of the animation value in the egui `Context`.
+    ///
+    /// By default every animated `Colorix` gets a unique id, so several of them can animate at once.
+    /// A fixed id keeps the animation stable across restarts of the app. It is shared by clones
+    /// and can be set before or after `animated`.
+    #[must_use]
+    pub fn animation_id(mut self, id: impl std::hash::Hash) -> Self {
+        self.animator.set_id(egui::Id::new(id));
+        self
+    }
+    /// Sets the curve along which the animation progresses, `Linear` by default.
+    #[must_use]
+    pub fn easing(mut self, easing: Easing) -> Self {
+        if self.animated {
+            self.animator.set_easing(easing);
+        }
+        self
+    }
+    /// Staggers the animation, so backgrounds transition before borders and text.
+    ///
+    /// `stagger` is the fraction of the animation time (up to 0.9) by which the
+    /// last step of the scale starts later than the first.
+    #[must_use]
+    pub const fn stagger(mut self, stagger: f32) -> Self {
+        if self.animated {
+            self.animator.set_stagger(stagger);
+        }
+        self
+    }
 
     /// sets new theme and animates towards it.
     pub fn update_theme(&mut self, ctx: &egui::Context, theme: Theme) {
         self.theme = theme;
+        self.mode_themes = None;
         self.get_theme_index();
         self.update_colors(Some(ctx), None);
     }
-    // starts animation; has only visible effect on the tokenshifts.
-    pub fn shift_tokens(&mut self, ctx: &egui::Context) {
-        self.animator.start(ctx);
+    /// Sets the rounding and stroke widths of the widgets. The shape isn't animated.
+    /// A global Colorix applies it right away, the others when they next style the ui.
+    pub fn set_shape(&mut self, ctx: &Context, shape: StyleShape) {
+        self.shape = shape;
+        if self.apply_to == ApplyTo::Global {
+            self.set_ctx_visuals(ctx);
+        }
+    }
+    /// Rounding and stroke widths of the widgets
+    #[must_use]
+    pub const fn shape(&self) -> &StyleShape {
+        &self.shape
+    }
+    /// Sets which token colors which part of the ui. The mapping isn't animated.
+    /// A global Colorix applies it right away, the others when they next style the ui.
+    pub fn set_mapping(&mut self, ctx: &Context, mapping: TokenMapping) {
+        self.mapping = mapping;
+        if self.apply_to == ApplyTo::Global {
+            self.set_ctx_visuals(ctx);
+        }
+    }
+    /// Which token colors which part of the ui
+    #[must_use]
+    pub const fn mapping(&self) -> &TokenMapping {
+        &self.mapping
+    }
+    /// Registers the scale of a color under a name, replacing a scale with the same name.
+    /// The scale follows the light/dark mode of the main theme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{scales::generate_scale, tokens::ThemeColor, utils::WARM, Colorix};
+    /// let mut colorix = Colorix::local_from_style(WARM, true);
+    /// colorix.register_scale("danger", ThemeColor::Red);
+    ///
+    /// let solid_danger = colorix.scale("danger")[8];
+    /// assert_eq!(solid_danger, generate_scale(ThemeColor::Red.rgb(), true)[8]);
+    /// ```
+    pub fn register_scale(&mut self, name: impl Into<String>, color: ThemeColor) {
+        let scale = generate_scale(color.rgb(), self.dark_mode());
+        self.named_scales.insert(name.into(), (color, scale));
+    }
+    /// The full 12 step scale of every color of the theme in the current mode,
+    /// in the order the colors first appear in the theme, e.g. for charts and legends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{tokens::{Step, ThemeColor}, Colorix};
+    /// let mut theme = [ThemeColor::Gray; 12];
+    /// theme[8] = ThemeColor::Indigo;
+    /// let colorix = Colorix::local_from_style(theme, true);
+    ///
+    /// let scales = colorix.theme_scales();
+    /// assert_eq!(scales.len(), 2);
+    /// assert_eq!(scales[1].0, ThemeColor::Indigo);
+    /// assert_eq!(scales[1].1[Step::SolidBackgrounds.index()], colorix.tokens.solid_backgrounds());
+    /// ```
+    #[must_use]
+    pub fn theme_scales(&self) -> Vec<(ThemeColor, [Color32; 12])> {
+        let mut scales: Vec<(ThemeColor, [Color32; 12])> = vec![];
+        for color in self.theme {
+            if !scales.iter().any(|(other, _)| *other == color) {
+                scales.push((color, generate_scale(color.rgb(), self.dark_mode())));
+            }
+        }
+        scales
+    }
+    /// The 12 step scale registered under a name, transparent if there is none.
+    #[must_use]
+    pub fn scale(&self, name: &str) -> [Color32; 12] {
+        self.named_scales
+            .get(name)
+            .map_or([Color32::TRANSPARENT; 12], |(_, scale)| *scale)
+    }
+    fn update_named_scales(&mut self) {
+        let dark_mode = self.dark_mode();
+        for (color, scale) in self.named_scales.values_mut() {
+            *scale = generate_scale(color.rgb(), dark_mode);
+        }
+    }
+    /// Adds a keyframe track under a name, replacing a track with the same name.
+    pub fn add_track(&mut self, name: impl Into<String>, track: ColorTrack) {
+        self.tracks.insert(name.into(), (track, None));
+    }
+    /// Plays a track from its first keyframe.
+    pub fn play_track(&mut self, ctx: &Context, name: &str) {
+        if let Some((_, start)) = self.tracks.get_mut(name) {
+            *start = Some(ctx.input(|i| i.time));
+            ctx.request_repaint();
+        }
+    }
+    /// Stops a track, it stays at its first keyframe.
+    pub fn stop_track(&mut self, name: &str) {
+        if let Some((_, start)) = self.tracks.get_mut(name) {
+            *start = None;
+        }
+    }
+    /// The current color of a track, resolved against the (animated) tokens.
+    /// Keeps repainting while the track is playing.
+    #[must_use]
+    pub fn track_color(&self, ctx: &Context, name: &str) -> Option<Color32> {
+        let (track, start) = self.tracks.get(name)?;
+        let tokens = if self.animated {
+            &self.animator.animated_tokens
+        } else {
+            &self.tokens
+        };
+        let elapsed = start.map_or(0.0, |start| (ctx.input(|i| i.time) - start) as f32);
+        if start.is_some() && !track.is_finished(elapsed) {
+            ctx.request_repaint();
+        }
+        Some(track.sample(tokens, elapsed))
     }
     #[must_use]
     pub const fn dark_mode(&self) -> bool {
         self.scales.dark_mode
     }
+    /// Whether an animated transition is running, see [`ColorAnimator`] for its progress and events.
+    #[must_use]
+    pub const fn is_animating(&self) -> bool {
+        self.animated && self.animator.is_animating()
+    }
 
     fn init_animator(&mut self) {
-        self.animator = ColorAnimator::new(self.tokens);
+        let fixed_id = self.animator.fixed_id();
+        self.animator = ColorAnimator::new(&self.tokens);
         self.animator.apply_to = self.apply_to;
+        if let Some(id) = fixed_id {
+            self.animator.set_id(id);
+        }
     }
 
     /// Necessary to engage the color animation
@@ -163,27 +370,29 @@ impl Colorix {
         match self.apply_to {
             ApplyTo::Global | ApplyTo::ExtraScale => {
                 if self.animated {
-                    self.animator.set_animate(Some(ctx), None, self.tokens);
+                    self.animator
+                        .set_animate(ctx, &self.tokens, &self.shape, &self.mapping);
                 }
             }
-            ApplyTo::Local => {}
+            ApplyTo::Local | ApplyTo::Scope => {}
         }
     }
 
     fn get_theme_index(&mut self) {
         if let Some(i) = THEMES.iter().position(|t| t == &self.theme) {
             self.theme_index = i;
-        };
+        }
     }
     /// create theme based on 1 custom color from color picker
     pub fn twelve_from_custom(&mut self, ui: &mut Ui) {
         self.theme = [ThemeColor::Custom(self.scales.custom()); 12];
+        self.mode_themes = None;
         self.match_and_update_colors(ui);
     }
 
     fn match_and_update_colors(&mut self, ui: &mut Ui) {
         match self.apply_to {
-            ApplyTo::Global | ApplyTo::ExtraScale => {
+            ApplyTo::Global | ApplyTo::Scope | ApplyTo::ExtraScale => {
                 self.update_colors(Some(ui.ctx()), None);
             }
             ApplyTo::Local => {
@@ -192,36 +401,24 @@ impl Colorix {
         }
     }
     fn set_colorix_mode(&mut self, mode: bool) {
+        if let Some(themes) = self.mode_themes {
+            self.theme = themes[usize::from(mode)];
+        }
         self.scales.dark_mode = mode;
         self.tokens.dark_mode = mode;
+        // the status and named scales only depend on the mode
+        self.tokens.update_status();
+        self.update_named_scales();
     }
 
-    pub fn update_locally(&mut self, ui: &mut Ui) {
-        if self.apply_to == ApplyTo::Local {
-            if self.animated {
-                self.animator.set_animate(None, Some(ui), self.tokens);
-            } else {
-                self.update_colors(None, Some(ui));
-            }
-        }
-    }
-
-    fn set_ui_mode(&self, ui: &mut Ui, mode: bool) {
-        match self.apply_to {
-            ApplyTo::Global => ui.ctx().style_mut(|style| style.visuals.dark_mode = mode),
-            ApplyTo::Local => ui.style_mut().visuals.dark_mode = mode,
-            ApplyTo::ExtraScale => {}
-        }
-    }
-
+    /// Switches to dark mode. When animated, the light and dark scales of the theme cross-fade.
     pub fn set_dark(&mut self, ui: &mut Ui) {
         self.set_colorix_mode(true);
-        self.set_ui_mode(ui, true);
         self.match_and_update_colors(ui);
     }
+    /// Switches to light mode. When animated, the light and dark scales of the theme cross-fade.
     pub fn set_light(&mut self, ui: &mut Ui) {
         self.set_colorix_mode(false);
-        self.set_ui_mode(ui, false);
         self.match_and_update_colors(ui);
     }
 
@@ -244,15 +441,41 @@ impl Colorix {
         }
     }
 
+    // the tokens that are shown, they trail `tokens` while animating
+    const fn shown_tokens(&self) -> &ColorTokens {
+        if self.animated {
+            &self.animator.animated_tokens
+        } else {
+            &self.tokens
+        }
+    }
+    fn set_egui_style(&self, style: &mut egui::Style) {
+        style.visuals = self
+            .shown_tokens()
+            .to_visuals(&style.visuals, &self.shape, &self.mapping);
+    }
+    fn set_ctx_visuals(&self, ctx: &Context) {
+        ctx.style_mut(|style| self.set_egui_style(style));
+    }
+    fn set_ui_visuals(&self, ui: &mut Ui) {
+        self.set_egui_style(ui.style_mut());
+    }
+
     fn match_egui_visuals(&self, ui: &mut Ui) {
         match self.apply_to {
-            ApplyTo::Global => self.tokens.set_ctx_visuals(ui.ctx()),
-            ApplyTo::Local => self.tokens.set_ui_visuals(ui),
-            ApplyTo::ExtraScale => {}
+            ApplyTo::Global => self.set_ctx_visuals(ui.ctx()),
+            ApplyTo::Local => self.set_ui_visuals(ui),
+            ApplyTo::Scope | ApplyTo::ExtraScale => {}
         }
     }
 
     fn update_color(&mut self, ui: &mut Ui, i: usize) {
+        // the edited slot replaces the slot of a theme file in both modes
+        if let Some(themes) = &mut self.mode_themes {
+            for theme in themes {
+                theme[i] = self.theme[i];
+            }
+        }
         self.scales.process_color(self.theme[i]);
         self.tokens.update_schema(i, self.scales.scale[i]);
         self.tokens.color_on_accent();
@@ -276,11 +499,11 @@ impl Colorix {
             self.process_theme();
             self.tokens.color_on_accent();
             if let Some(ctx) = ctx {
-                if self.apply_to != ApplyTo::ExtraScale {
-                    self.tokens.set_ctx_visuals(ctx);
+                if self.apply_to == ApplyTo::Global {
+                    self.set_ctx_visuals(ctx);
                 }
             } else if let Some(ui) = ui {
-                self.tokens.set_ui_visuals(ui);
+                self.set_ui_visuals(ui);
             }
         }
     }
@@ -299,9 +522,7 @@ impl Colorix {
                 .on_hover_text("Switch to light mode")
                 .clicked()
             {
-                self.set_colorix_mode(false);
-                self.set_ui_mode(ui, false);
-                self.match_and_update_colors(ui);
+                self.set_light(ui);
             }
         } else {
             if ui
@@ -313,9 +534,7 @@ impl Colorix {
                 .on_hover_text("Switch to dark mode")
                 .clicked()
             {
-                self.set_colorix_mode(true);
-                self.set_ui_mode(ui, true);
-                self.match_and_update_colors(ui);
+                self.set_dark(ui);
             }
         }
     }
@@ -365,38 +584,18 @@ impl Colorix {
                         .clicked()
                     {
                         self.theme_index = i;
+                        self.mode_themes = None;
                         self.match_and_update_colors(ui);
-                    };
+                    }
                 }
             });
     }
     /// A widget with 12 dropdown menus of the UI elements (`ColorTokens`) that can be set.
+    /// With `copy` the theme can be copied to the clipboard and a copied theme
+    /// (or a list of 12 presets and hex colors) can be pasted back.
     pub fn ui_combo_12(&mut self, ui: &mut Ui, copy: bool) {
-        let dropdown_colors: [ThemeColor; 23] = [
-            ThemeColor::Gray,
-            ThemeColor::EguiBlue,
-            ThemeColor::Tomato,
-            ThemeColor::Red,
-            ThemeColor::Ruby,
-            ThemeColor::Crimson,
-            ThemeColor::Pink,
-            ThemeColor::Plum,
-            ThemeColor::Purple,
-            ThemeColor::Violet,
-            ThemeColor::Iris,
-            ThemeColor::Indigo,
-            ThemeColor::Blue,
-            ThemeColor::Cyan,
-            ThemeColor::Teal,
-            ThemeColor::Jade,
-            ThemeColor::Green,
-            ThemeColor::Grass,
-            ThemeColor::Brown,
-            ThemeColor::Bronze,
-            ThemeColor::Gold,
-            ThemeColor::Orange,
-            ThemeColor::Custom(self.scales.custom()),
-        ];
+        let mut dropdown_colors = [ThemeColor::Custom(self.scales.custom()); 23];
+        dropdown_colors[..PRESETS.len()].copy_from_slice(&PRESETS);
         ui.vertical(|ui| {
             for (i, label) in LABELS.iter().enumerate() {
                 ui.horizontal(|ui| {
@@ -426,7 +625,7 @@ impl Colorix {
                                     .clicked()
                                 {
                                     self.update_color(ui, i);
-                                };
+                                }
                             }
                         });
                 });
@@ -437,6 +636,25 @@ impl Colorix {
             if ui.button("Copy theme to clipboard").clicked() {
                 ui.output_mut(|out| out.copied_text = format!("{:#?}", self.theme));
             }
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.import_text).hint_text("Paste theme"));
+                if ui.button("Import theme").clicked() {
+                    match parse::parse_theme(&self.import_text) {
+                        Ok(theme) => {
+                            self.theme = theme;
+                            self.mode_themes = None;
+                            self.get_theme_index();
+                            self.match_and_update_colors(ui);
+                            self.import_text.clear();
+                            self.import_error = None;
+                        }
+                        Err(err) => self.import_error = Some(err.to_string()),
+                    }
+                }
+            });
+            if let Some(err) = &self.import_error {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
         }
     }
 
@@ -451,7 +669,7 @@ impl Colorix {
         .changed()
         {
             self.scales.clamp_custom();
-        };
+        }
     }
 
     /// Set a background gradient. Choose 'true' for color from `solid_backgrounds` (if animated `active_ui_element_background`)
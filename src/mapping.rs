@@ -0,0 +1,109 @@
+use crate::tokens::Token;
+
+/// The tokens that color a widget in one interaction state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WidgetMapping {
+    pub weak_bg_fill: Token,
+    pub bg_fill: Token,
+    /// border
+    pub bg_stroke: Token,
+    /// text and icons
+    pub fg_stroke: Token,
+}
+
+impl WidgetMapping {
+    const fn new(bg_fill: Token, bg_stroke: Token, fg_stroke: Token) -> Self {
+        Self {
+            weak_bg_fill: bg_fill,
+            bg_fill,
+            bg_stroke,
+            fg_stroke,
+        }
+    }
+}
+
+/// Which token feeds which color of `egui::Visuals`.
+///
+/// The default is the mapping of the Radix scale, the error and warning colors and the shadows
+/// are derived from the status scales and the tokens.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{mapping::TokenMapping, shape::StyleShape, tokens::{ColorTokens, Token}, utils::WARM};
+/// let mut mapping = TokenMapping::default();
+/// mapping.inactive.bg_stroke = Token::UiElementBorderAndFocusRings;
+///
+/// let tokens = ColorTokens::from_theme(&WARM, true);
+/// let visuals = tokens.to_visuals(&egui::Visuals::dark(), &StyleShape::default(), &mapping);
+/// assert_eq!(
+///     visuals.widgets.inactive.bg_stroke.color,
+///     tokens.ui_element_border_and_focus_rings()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenMapping {
+    pub noninteractive: WidgetMapping,
+    pub inactive: WidgetMapping,
+    pub hovered: WidgetMapping,
+    pub active: WidgetMapping,
+    pub open: WidgetMapping,
+    pub selection_bg_fill: Token,
+    pub selection_stroke: Token,
+    pub text_cursor: Token,
+    /// e.g. `TextEdit` background
+    pub extreme_bg_color: Token,
+    /// e.g. striped grid
+    pub faint_bg_color: Token,
+    pub code_bg_color: Token,
+    pub window_fill: Token,
+    pub window_stroke: Token,
+    pub panel_fill: Token,
+    pub hyperlink_color: Token,
+}
+
+impl Default for TokenMapping {
+    fn default() -> Self {
+        Self {
+            // separators, indentation lines and normal text
+            noninteractive: WidgetMapping::new(
+                Token::SubtleBackground,
+                Token::SubtleBordersAndSeparators,
+                Token::LowContrastText,
+            ),
+            // button and checkbox background
+            inactive: WidgetMapping::new(
+                Token::UiElementBackground,
+                Token::UiElementBackground,
+                Token::LowContrastText,
+            ),
+            hovered: WidgetMapping::new(
+                Token::HoveredUiElementBackground,
+                Token::HoveredUiElementBorder,
+                Token::HighContrastText,
+            ),
+            active: WidgetMapping::new(
+                Token::ActiveUiElementBackground,
+                Token::UiElementBorderAndFocusRings,
+                Token::HighContrastText,
+            ),
+            open: WidgetMapping::new(
+                Token::ActiveUiElementBackground,
+                Token::UiElementBorderAndFocusRings,
+                Token::HighContrastText,
+            ),
+            selection_bg_fill: Token::SolidBackgrounds,
+            selection_stroke: Token::OnAccent,
+            text_cursor: Token::LowContrastText,
+            extreme_bg_color: Token::AppBackground,
+            faint_bg_color: Token::AppBackground,
+            code_bg_color: Token::UiElementBackground,
+            window_fill: Token::SubtleBackground,
+            window_stroke: Token::SubtleBordersAndSeparators,
+            panel_fill: Token::SubtleBackground,
+            hyperlink_color: Token::HoveredSolidBackgrounds,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    color_space::{from_degrees, oklch_to_u8, Okhsl},
+    tokens::ThemeColor,
+    utils::PRESETS,
+    Theme,
+};
+use egui::{ecolor::HexColor, Color32};
+use std::str::FromStr;
+
+/// Errors that can occur when parsing a [`ThemeColor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// Not a valid opaque `#rgb` or `#rrggbb` color.
+    InvalidHex(String),
+    /// The arguments of `rgb()`, `oklch()` or `okhsl()` are invalid.
+    InvalidFunction(String),
+    /// Not a color function and no preset with this name exists.
+    UnknownName(String),
+}
+
+impl std::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHex(s) => write!(f, "invalid hex color `{s}`"),
+            Self::InvalidFunction(s) => write!(f, "invalid color function `{s}`"),
+            Self::UnknownName(s) => write!(f, "unknown color `{s}`"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+// a number, a percentage of `percent_scale`, or an angle in degrees
+fn parse_number(s: &str, percent_scale: f32) -> Option<f32> {
+    let value = if let Some(percent) = s.strip_suffix('%') {
+        percent.parse::<f32>().ok()? / 100. * percent_scale
+    } else {
+        s.strip_suffix("deg").unwrap_or(s).parse().ok()?
+    };
+    value.is_finite().then_some(value)
+}
+
+fn parse_function(name: &str, args: &str) -> Option<[u8; 3]> {
+    let args: Vec<&str> = args
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    let [a, b, c] = args[..] else { return None };
+    match name {
+        "rgb" => {
+            let channel = |s| {
+                parse_number(s, 255.)
+                    .filter(|v| (0.0..=255.).contains(v))
+                    .map(|v| v.round() as u8)
+            };
+            Some([channel(a)?, channel(b)?, channel(c)?])
+        }
+        "oklch" => Some(oklch_to_u8(
+            parse_number(a, 1.)?.clamp(0., 1.),
+            parse_number(b, 0.4)?.max(0.),
+            parse_number(c, 360.)?,
+        )),
+        "okhsl" => Some(
+            Okhsl {
+                hue: from_degrees(parse_number(a, 360.)?.rem_euclid(360.)),
+                saturation: parse_number(b, 1.)?.clamp(0., 1.),
+                lightness: parse_number(c, 1.)?.clamp(0., 1.),
+            }
+            .to_u8(),
+        ),
+        _ => None,
+    }
+}
+
+/// Parses `#rrggbb`, `#rgb`, `rgb(r, g, b)`, `oklch(l c h)`, `okhsl(h s l)` or a preset name (case insensitive).
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::tokens::ThemeColor;
+/// assert_eq!("tomato".parse(), Ok(ThemeColor::Tomato));
+/// assert_eq!("#e8d207".parse(), Ok(ThemeColor::Custom([232, 210, 7])));
+/// assert_eq!("rgb(232, 210, 7)".parse(), Ok(ThemeColor::Custom([232, 210, 7])));
+/// assert!("oklch(0.7 0.15 250)".parse::<ThemeColor>().is_ok());
+/// ```
+impl FromStr for ThemeColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(digits) = s.strip_prefix('#') {
+            // `from_hex` also accepts `#rgba` and `#rrggbbaa`
+            return matches!(digits.len(), 3 | 6)
+                .then(|| Color32::from_hex(s).ok())
+                .flatten()
+                .map(|color| Self::Custom([color.r(), color.g(), color.b()]))
+                .ok_or_else(|| ParseColorError::InvalidHex(s.to_string()));
+        }
+        if let Some((name, args)) = s.strip_suffix(')').and_then(|s| s.split_once('(')) {
+            return parse_function(&name.trim().to_ascii_lowercase(), args)
+                .map(Self::Custom)
+                .ok_or_else(|| ParseColorError::InvalidFunction(s.to_string()));
+        }
+        PRESETS
+            .iter()
+            .copied()
+            .find(|preset| preset.label().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseColorError::UnknownName(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for ThemeColor {
+    type Error = ParseColorError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Writes the preset name, or `#rrggbb` for custom colors.
+/// The output can be parsed back with `FromStr`.
+impl std::fmt::Display for ThemeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Custom([r, g, b]) => HexColor::Hex6(Color32::from_rgb(r, g, b)).fmt(f),
+            preset => f.write_str(preset.label()),
+        }
+    }
+}
+
+/// Errors that can occur when parsing a theme from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseThemeError {
+    /// A value is neither a `Custom([r, g, b])` nor a preset name or hex color.
+    InvalidColor(ParseColorError),
+    /// A `Custom` color doesn't consist of 3 values between 0 and 255.
+    InvalidCustom,
+    /// The text doesn't contain exactly 12 colors.
+    WrongColorCount(usize),
+}
+
+impl std::fmt::Display for ParseThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidColor(err) => err.fmt(f),
+            Self::InvalidCustom => write!(f, "a custom color needs 3 values between 0 and 255"),
+            Self::WrongColorCount(count) => write!(f, "expected 12 colors, found {count}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseThemeError {}
+
+/// Parses a theme as copied by [`Colorix::ui_combo_12`](crate::Colorix::ui_combo_12),
+/// or a list of 12 preset names and `#rrggbb` hex colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{parse::parse_theme, tokens::ThemeColor};
+/// let theme = parse_theme("[Gray, Gray, Gray, Gray, Gray, Gray, Gray, Gray, Custom([232, 210, 7]), #e8d207, Gray, Gray]");
+/// assert_eq!(theme.map(|t| t[8]), Ok(ThemeColor::Custom([232, 210, 7])));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a color can't be parsed or the number of colors isn't 12.
+pub fn parse_theme(text: &str) -> Result<Theme, ParseThemeError> {
+    let mut colors = vec![];
+    let mut words = text
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '[' | ']' | '(' | ')'))
+        .filter(|word| !word.is_empty());
+    while let Some(word) = words.next() {
+        let color = if word == "Custom" {
+            let mut rgb = [0; 3];
+            for v in &mut rgb {
+                *v = words
+                    .next()
+                    .and_then(|word| word.parse().ok())
+                    .ok_or(ParseThemeError::InvalidCustom)?;
+            }
+            ThemeColor::Custom(rgb)
+        } else {
+            word.parse().map_err(ParseThemeError::InvalidColor)?
+        };
+        colors.push(color);
+    }
+    colors
+        .try_into()
+        .map_err(|colors: Vec<ThemeColor>| ParseThemeError::WrongColorCount(colors.len()))
+}
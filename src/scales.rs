@@ -6,8 +6,91 @@ use crate::color_space::{from_degrees, LinSrgb, Okhsl};
 use crate::{apca::estimate_lc, tokens::ThemeColor};
 use egui::{epaint::Hsva, Color32};
 
+/// Computes the 12 step scale of a rgb color, as shown by a `Colorix` in the same mode.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{scales::generate_scale, tokens::ThemeColor, Colorix};
+/// let scale = generate_scale(ThemeColor::Indigo.rgb(), true);
+/// let solid_background = scale[8];
+///
+/// let colorix = Colorix::local_from_style([ThemeColor::Indigo; 12], true);
+/// assert_eq!(solid_background, colorix.tokens.solid_backgrounds());
+/// ```
+#[must_use]
+pub fn generate_scale(rgb: [u8; 3], dark_mode: bool) -> [Color32; 12] {
+    compute_scale(rgb, dark_mode).scale
+}
+
+/// Same as [`generate_scale`], but returns the steps as `[u8; 3]` rgb values.
+#[must_use]
+pub fn generate_scale_rgb(rgb: [u8; 3], dark_mode: bool) -> [[u8; 3]; 12] {
+    compute_scale(rgb, dark_mode).okhsl.map(Okhsl::to_u8)
+}
+
+/// Same as [`generate_scale`], but returns the steps as Okhsl values.
+///
+/// Every step is `[hue, saturation, lightness]` with the hue in degrees.
+#[must_use]
+pub fn generate_scale_okhsl(rgb: [u8; 3], dark_mode: bool) -> [[f32; 3]; 12] {
+    compute_scale(rgb, dark_mode)
+        .okhsl
+        .map(|hsl| [hsl.as_degrees(), hsl.saturation, hsl.lightness])
+}
+
+/// Same as [`generate_scale`], but every step is translucent and looks identical to the solid step
+/// when drawn over the first step (the app background) of the scale.
+#[must_use]
+pub fn generate_alpha_scale(rgb: [u8; 3], dark_mode: bool) -> [Color32; 12] {
+    let scale = generate_scale(rgb, dark_mode);
+    scale.map(|color| alpha_color(color, scale[0]))
+}
+
+/// The most transparent color that looks identical to `color` when drawn over `background`.
+#[must_use]
+pub fn alpha_color(color: Color32, background: Color32) -> Color32 {
+    let color = color.to_array();
+    let background = background.to_array();
+    // the lowest alpha that can reach every channel of the color from the background
+    let alpha = (0..3)
+        .map(|i| {
+            let (c, bg) = (color[i] as f32, background[i] as f32);
+            if c > bg {
+                (c - bg) / (255. - bg)
+            } else if c < bg {
+                (bg - c) / bg
+            } else {
+                0.
+            }
+        })
+        .fold(0., f32::max);
+    let alpha = (alpha * 255.).ceil().clamp(0., 255.) as u8;
+    if alpha == 0 {
+        return Color32::TRANSPARENT;
+    }
+    // egui blends premultiplied colors in gamma space: color = premultiplied + background * (1 - alpha)
+    let a = alpha as f32 / 255.;
+    let [r, g, b] = [0, 1, 2].map(|i| {
+        (background[i] as f32)
+            .mul_add(a - 1., color[i] as f32)
+            .round()
+            .clamp(0., f32::from(alpha)) as u8
+    });
+    Color32::from_rgba_premultiplied(r, g, b, alpha)
+}
+
+fn compute_scale(rgb: [u8; 3], dark_mode: bool) -> Scales {
+    let mut scales = Scales {
+        dark_mode,
+        ..Default::default()
+    };
+    scales.process_color(ThemeColor::Custom(rgb));
+    scales
+}
+
 #[derive(Debug, Default, Clone)]
-pub struct Scales {
+pub(crate) struct Scales {
     pub custom: Hsva,
     pub okhsl: [Okhsl; 12],
     pub rgbs: [LinSrgb; 12],
@@ -0,0 +1,153 @@
+use crate::{
+    mapping::TokenMapping, shape::StyleShape, tokens::ColorTokens, ApplyTo, Colorix, Theme,
+};
+use egui::{Context, Id, InnerResponse, Style, Ui, UiBuilder};
+use std::sync::Arc;
+
+// the tokens of the themes shown most recently with `Colorix::show_scoped`, kept in the egui memory
+type ScopedTokens = Vec<((Theme, bool), ColorTokens)>;
+// a theme that follows a color picker adds an entry per color, the oldest entries are dropped
+const SCOPED_TOKENS_CAPACITY: usize = 16;
+
+// the style of a scope, reused as long as the parent style and the shown tokens don't change
+#[derive(Debug, Clone)]
+pub struct ScopeStyle {
+    parent: Arc<Style>,
+    tokens: ColorTokens,
+    shape: StyleShape,
+    mapping: TokenMapping,
+    style: Arc<Style>,
+}
+
+impl Colorix {
+    /// Initialize a Colorix that applies to the ui inside [`Self::scope`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut sidebar = Colorix::scoped(ctx, utils::WARM).animated();
+    ///
+    /// // every frame
+    /// sidebar.scope(ui, |ui| {
+    ///     ui.label("warm colors");
+    /// });
+    /// ```
+    #[must_use]
+    pub fn scoped(ctx: &Context, theme: Theme) -> Self {
+        let mut colorix = Self {
+            theme,
+            ..Default::default()
+        };
+        let mode = ctx.style().visuals.dark_mode;
+        colorix.set_colorix_mode(mode);
+        colorix.get_theme_index();
+        colorix.apply_to = ApplyTo::Scope;
+        colorix.update_colors(Some(ctx), None);
+        colorix
+    }
+
+    /// Shows `add_contents` in a child ui with the colors of this Colorix and drives its animation.
+    ///
+    /// The child ui has the same id every frame, also when the same parent ui is reused.
+    /// The style is only rebuilt while animating or when the parent style changes,
+    /// so nested themed panels are cheap.
+    pub fn scope<R>(
+        &mut self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let id = self.animator.id(ui.ctx());
+        if self.animated {
+            self.animator.tick(ui.ctx(), &self.tokens);
+        }
+        let style = self.scope_style(ui.style());
+        ui.scope_builder(UiBuilder::new().id_salt(id), |ui| {
+            ui.set_style(style);
+            add_contents(ui)
+        })
+    }
+
+    /// Styles `ui` with the colors of a local Colorix and drives its animation, call it every frame.
+    ///
+    /// Like [`Self::scope`] the style is only rebuilt while animating or when the style of `ui` changes.
+    pub fn update_locally(&mut self, ui: &mut Ui) {
+        if self.apply_to == ApplyTo::Local {
+            if self.animated {
+                self.animator.id(ui.ctx());
+                self.animator.tick(ui.ctx(), &self.tokens);
+            }
+            let style = self.scope_style(ui.style());
+            ui.set_style(style);
+        }
+    }
+
+    /// Shows `add_contents` with the colors of `theme`, without a `Colorix` to own.
+    /// The style of the parent ui is restored afterwards.
+    ///
+    /// The tokens are built once per theme and mode (the mode of the parent ui) and kept in the egui memory,
+    /// for the 16 most recently shown themes.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// Colorix::show_scoped(ui, utils::WARM, |ui| {
+    ///     ui.label("a panel with a warm accent");
+    /// });
+    /// ```
+    pub fn show_scoped<R>(
+        ui: &mut Ui,
+        theme: Theme,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let dark_mode = ui.style().visuals.dark_mode;
+        let key = (theme, dark_mode);
+        let tokens = ui.ctx().data_mut(|data| {
+            let cache =
+                data.get_temp_mut_or_default::<ScopedTokens>(Id::new("egui_colors scoped themes"));
+            let tokens = cache.iter().position(|(k, _)| *k == key).map_or_else(
+                || ColorTokens::from_theme(&theme, dark_mode),
+                |i| cache.remove(i).1,
+            );
+            if cache.len() == SCOPED_TOKENS_CAPACITY {
+                cache.remove(0);
+            }
+            cache.push((key, tokens));
+            tokens
+        });
+        ui.scope(|ui| {
+            tokens.set_egui_style(ui.style_mut());
+            add_contents(ui)
+        })
+    }
+
+    fn scope_style(&mut self, parent: &Arc<Style>) -> Arc<Style> {
+        if self.is_animating() {
+            let mut style = (**parent).clone();
+            self.animator
+                .set_egui_animation(&mut style, &self.shape, &self.mapping);
+            return Arc::new(style);
+        }
+        if let Some(cached) = &self.scope_style {
+            if cached.tokens == self.tokens
+                && cached.shape == self.shape
+                && cached.mapping == self.mapping
+                && (Arc::ptr_eq(&cached.parent, parent) || cached.parent == *parent)
+            {
+                return cached.style.clone();
+            }
+        }
+        let mut style = (**parent).clone();
+        style.visuals = self
+            .tokens
+            .to_visuals(&style.visuals, &self.shape, &self.mapping);
+        let style = Arc::new(style);
+        self.scope_style = Some(ScopeStyle {
+            parent: parent.clone(),
+            tokens: self.tokens,
+            shape: self.shape,
+            mapping: self.mapping,
+            style: style.clone(),
+        });
+        style
+    }
+}
@@ -0,0 +1,83 @@
+use egui::Rounding;
+
+/// Geometry of a widget in one interaction state
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WidgetShape {
+    pub rounding: Rounding,
+    /// width of the border
+    pub bg_stroke_width: f32,
+    /// width of text and icon strokes
+    pub fg_stroke_width: f32,
+    /// how much the widget grows, e.g. when hovered
+    pub expansion: f32,
+}
+
+impl WidgetShape {
+    const fn new(rounding: f32, fg_stroke_width: f32, expansion: f32) -> Self {
+        Self {
+            rounding: Rounding::same(rounding),
+            bg_stroke_width: 1.0,
+            fg_stroke_width,
+            expansion,
+        }
+    }
+}
+
+/// Geometry of the widgets that is applied together with the colors.
+///
+/// # Examples
+///
+/// ```ignore
+/// app.colorix.set_shape(ctx, StyleShape::pill());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyleShape {
+    pub noninteractive: WidgetShape,
+    pub inactive: WidgetShape,
+    pub hovered: WidgetShape,
+    pub active: WidgetShape,
+    pub open: WidgetShape,
+    pub selection_stroke_width: f32,
+    pub text_cursor_width: f32,
+    pub window_stroke_width: f32,
+}
+
+impl Default for StyleShape {
+    fn default() -> Self {
+        Self {
+            noninteractive: WidgetShape::new(2.0, 1.0, 0.0),
+            inactive: WidgetShape::new(2.0, 1.0, 0.0),
+            hovered: WidgetShape::new(3.0, 1.5, 1.0),
+            active: WidgetShape::new(2.0, 2.0, 1.0),
+            open: WidgetShape::new(2.0, 1.0, 0.0),
+            selection_stroke_width: 1.0,
+            text_cursor_width: 2.0,
+            window_stroke_width: 1.0,
+        }
+    }
+}
+
+impl StyleShape {
+    /// No rounding at all
+    #[must_use]
+    pub fn sharp() -> Self {
+        Self::default().with_rounding(Rounding::ZERO)
+    }
+    /// Fully rounded ends
+    #[must_use]
+    pub fn pill() -> Self {
+        Self::default().with_rounding(Rounding::same(100.0))
+    }
+    /// Sets the same rounding for every widget state
+    #[must_use]
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.noninteractive.rounding = rounding;
+        self.inactive.rounding = rounding;
+        self.hovered.rounding = rounding;
+        self.active.rounding = rounding;
+        self.open.rounding = rounding;
+        self
+    }
+}
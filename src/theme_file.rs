@@ -0,0 +1,257 @@
+//! A versioned text format to exchange themes.
+//!
+//! Every slot is a color as parsed by [`ThemeColor`]'s `FromStr`: a preset name (`"Gold"`),
+//! a hex color (`"#12a594"`) or a css color function (`"oklch(0.65 0.12 180)"`).
+//! The optional `light` and `dark` tables override single slots for that mode,
+//! keyed by the token name.
+//!
+//! ```toml
+//! version = 1
+//! name = "Warm"
+//! author = "Jane Doe"
+//! colors = [
+//!     "Gray", "Gray", "Orange", "Gold", "Gold", "Gold",
+//!     "Red", "Red", "Gold", "Gold", "Gray", "#12a594",
+//! ]
+//!
+//! [dark]
+//! solid_backgrounds = "Orange"
+//! ```
+//!
+//! The same fields are used for JSON.
+use crate::{parse::ParseColorError, tokens::ThemeColor, utils::LABELS, Colorix, Theme};
+use egui::Context;
+use std::collections::BTreeMap;
+
+/// The version written by this crate. Files with a newer version, or version 0, are rejected.
+pub const THEME_FILE_VERSION: u32 = 1;
+
+/// Errors that can occur when reading or writing a theme file.
+#[derive(Debug)]
+pub enum ThemeFileError {
+    #[cfg(feature = "toml")]
+    TomlRead(toml::de::Error),
+    #[cfg(feature = "toml")]
+    TomlWrite(toml::ser::Error),
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The file was written by a newer version of this crate, or has no valid version.
+    UnsupportedVersion(u32),
+    /// `colors` doesn't contain exactly 12 entries.
+    WrongColorCount(usize),
+    /// A mode override uses a key that isn't a token name.
+    UnknownToken(String),
+    /// A value can't be parsed as a `ThemeColor`.
+    InvalidColor {
+        slot: String,
+        value: String,
+        source: ParseColorError,
+    },
+}
+
+impl std::fmt::Display for ThemeFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::TomlRead(err) => write!(f, "invalid toml: {err}"),
+            #[cfg(feature = "toml")]
+            Self::TomlWrite(err) => write!(f, "failed to write toml: {err}"),
+            #[cfg(feature = "json")]
+            Self::Json(err) => write!(f, "invalid json: {err}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "theme file version {version} is not supported (expected 1 to {THEME_FILE_VERSION})"
+            ),
+            Self::WrongColorCount(count) => {
+                write!(f, "expected 12 colors, found {count}")
+            }
+            Self::UnknownToken(key) => write!(f, "unknown token `{key}`"),
+            Self::InvalidColor { slot, value, .. } => {
+                write!(f, "invalid color `{value}` for `{slot}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::TomlRead(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::TomlWrite(err) => Some(err),
+            #[cfg(feature = "json")]
+            Self::Json(err) => Some(err),
+            Self::InvalidColor { source, .. } => Some(source),
+            Self::UnsupportedVersion(_) | Self::WrongColorCount(_) | Self::UnknownToken(_) => None,
+        }
+    }
+}
+
+/// A theme with its metadata, as stored in a theme file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeFile {
+    pub name: String,
+    pub author: Option<String>,
+    pub theme: Theme,
+    /// slots that differ in light mode
+    pub light: [Option<ThemeColor>; 12],
+    /// slots that differ in dark mode
+    pub dark: [Option<ThemeColor>; 12],
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RawThemeFile {
+    version: u32,
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    colors: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    light: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dark: BTreeMap<String, String>,
+}
+
+fn token_key(i: usize) -> String {
+    LABELS[i].replace(' ', "_")
+}
+
+fn parse_color(slot: String, value: &str) -> Result<ThemeColor, ThemeFileError> {
+    value
+        .parse()
+        .map_err(|source| ThemeFileError::InvalidColor {
+            slot,
+            value: value.to_string(),
+            source,
+        })
+}
+
+fn parse_overrides(
+    raw: &BTreeMap<String, String>,
+) -> Result<[Option<ThemeColor>; 12], ThemeFileError> {
+    let mut overrides = [None; 12];
+    for (key, value) in raw {
+        let i = (0..12)
+            .find(|i| &token_key(*i) == key)
+            .ok_or_else(|| ThemeFileError::UnknownToken(key.clone()))?;
+        overrides[i] = Some(parse_color(key.clone(), value)?);
+    }
+    Ok(overrides)
+}
+
+fn write_overrides(overrides: &[Option<ThemeColor>; 12]) -> BTreeMap<String, String> {
+    overrides
+        .iter()
+        .enumerate()
+        .filter_map(|(i, color)| color.map(|color| (token_key(i), color.to_string())))
+        .collect()
+}
+
+impl ThemeFile {
+    #[must_use]
+    pub fn new(name: impl Into<String>, theme: Theme) -> Self {
+        Self {
+            name: name.into(),
+            author: None,
+            theme,
+            light: [None; 12],
+            dark: [None; 12],
+        }
+    }
+
+    /// The theme with the overrides of the given mode applied.
+    #[must_use]
+    pub fn theme_for(&self, dark_mode: bool) -> Theme {
+        let overrides = if dark_mode { &self.dark } else { &self.light };
+        let mut theme = self.theme;
+        for (color, over) in theme.iter_mut().zip(overrides) {
+            if let Some(over) = over {
+                *color = *over;
+            }
+        }
+        theme
+    }
+
+    fn from_raw(raw: RawThemeFile) -> Result<Self, ThemeFileError> {
+        if !(1..=THEME_FILE_VERSION).contains(&raw.version) {
+            return Err(ThemeFileError::UnsupportedVersion(raw.version));
+        }
+        if raw.colors.len() != 12 {
+            return Err(ThemeFileError::WrongColorCount(raw.colors.len()));
+        }
+        let mut theme = Theme::default();
+        for (i, value) in raw.colors.iter().enumerate() {
+            theme[i] = parse_color(token_key(i), value)?;
+        }
+        Ok(Self {
+            name: raw.name,
+            author: raw.author,
+            theme,
+            light: parse_overrides(&raw.light)?,
+            dark: parse_overrides(&raw.dark)?,
+        })
+    }
+
+    fn to_raw(&self) -> RawThemeFile {
+        RawThemeFile {
+            version: THEME_FILE_VERSION,
+            name: self.name.clone(),
+            author: self.author.clone(),
+            colors: self.theme.iter().map(ThemeColor::to_string).collect(),
+            light: write_overrides(&self.light),
+            dark: write_overrides(&self.dark),
+        }
+    }
+
+    /// Parses a theme file in TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file isn't valid TOML or doesn't describe a valid theme.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, ThemeFileError> {
+        Self::from_raw(toml::from_str(s).map_err(ThemeFileError::TomlRead)?)
+    }
+
+    /// Writes the theme file as TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer fails.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, ThemeFileError> {
+        toml::to_string_pretty(&self.to_raw()).map_err(ThemeFileError::TomlWrite)
+    }
+
+    /// Parses a theme file in JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file isn't valid JSON or doesn't describe a valid theme.
+    #[cfg(feature = "json")]
+    pub fn from_json(s: &str) -> Result<Self, ThemeFileError> {
+        Self::from_raw(serde_json::from_str(s).map_err(ThemeFileError::Json)?)
+    }
+
+    /// Writes the theme file as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer fails.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, ThemeFileError> {
+        serde_json::to_string_pretty(&self.to_raw()).map_err(ThemeFileError::Json)
+    }
+}
+
+impl Colorix {
+    /// Sets the theme of a theme file and animates towards it.
+    /// The mode overrides of the file are kept when switching between light and dark mode.
+    pub fn update_theme_file(&mut self, ctx: &Context, file: &ThemeFile) {
+        self.mode_themes = Some([file.theme_for(false), file.theme_for(true)]);
+        self.theme = file.theme_for(self.dark_mode());
+        self.get_theme_index();
+        self.update_colors(Some(ctx), None);
+    }
+}
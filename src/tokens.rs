@@ -1,13 +1,19 @@
 use crate::apca::estimate_lc;
 use crate::color_space::LinSrgb;
+use crate::mapping::{TokenMapping, WidgetMapping};
+use crate::scales::{alpha_color, generate_scale};
+use crate::shape::{StyleShape, WidgetShape};
+use crate::Theme;
 use egui::{
     self,
-    style::{TextCursorStyle, WidgetVisuals},
-    Color32, Context, Rounding, Stroke, Ui,
+    style::{Selection, TextCursorStyle, WidgetVisuals, Widgets},
+    Color32, Shadow, Stroke, Visuals,
 };
+use std::ops::Index;
 
 /// The functional UI elements mapped to a scale
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorTokens {
     pub(crate) app_background: Color32,
     pub(crate) subtle_background: Color32,
@@ -24,6 +30,145 @@ pub struct ColorTokens {
     pub(crate) inverse_color: bool,
     pub(crate) on_accent: Color32,
     pub(crate) dark_mode: bool,
+    pub(crate) status: [[Color32; 12]; 4],
+}
+
+/// The name of a color token, in the order of the scale steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Token {
+    AppBackground,
+    SubtleBackground,
+    UiElementBackground,
+    HoveredUiElementBackground,
+    ActiveUiElementBackground,
+    SubtleBordersAndSeparators,
+    UiElementBorderAndFocusRings,
+    HoveredUiElementBorder,
+    SolidBackgrounds,
+    HoveredSolidBackgrounds,
+    LowContrastText,
+    HighContrastText,
+    /// Text and icons on top of `SolidBackgrounds`
+    OnAccent,
+}
+
+impl Token {
+    pub const ALL: [Self; 13] = [
+        Self::AppBackground,
+        Self::SubtleBackground,
+        Self::UiElementBackground,
+        Self::HoveredUiElementBackground,
+        Self::ActiveUiElementBackground,
+        Self::SubtleBordersAndSeparators,
+        Self::UiElementBorderAndFocusRings,
+        Self::HoveredUiElementBorder,
+        Self::SolidBackgrounds,
+        Self::HoveredSolidBackgrounds,
+        Self::LowContrastText,
+        Self::HighContrastText,
+        Self::OnAccent,
+    ];
+}
+
+/// A step of the 12 step scale, numbered from 1 to 12 and named after its use.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{tokens::{ColorTokens, Step}, utils::WARM};
+/// let tokens = ColorTokens::from_theme(&WARM, true);
+/// assert_eq!(tokens[Step::SolidBackgrounds], tokens.solid_backgrounds());
+/// assert_eq!(Step::SolidBackgrounds.number(), 9);
+/// assert_eq!(Step::from_number(9), Some(Step::SolidBackgrounds));
+///
+/// // a legend of the scale
+/// for (step, color) in Step::ALL.into_iter().zip(tokens.iter()) {
+///     println!("{} {step:?}: {color:?}", step.number());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Step {
+    AppBackground = 1,
+    SubtleBackground,
+    UiElementBackground,
+    HoveredUiElementBackground,
+    ActiveUiElementBackground,
+    SubtleBordersAndSeparators,
+    UiElementBorderAndFocusRings,
+    HoveredUiElementBorder,
+    SolidBackgrounds,
+    HoveredSolidBackgrounds,
+    LowContrastText,
+    HighContrastText,
+}
+
+impl Step {
+    pub const ALL: [Self; 12] = [
+        Self::AppBackground,
+        Self::SubtleBackground,
+        Self::UiElementBackground,
+        Self::HoveredUiElementBackground,
+        Self::ActiveUiElementBackground,
+        Self::SubtleBordersAndSeparators,
+        Self::UiElementBorderAndFocusRings,
+        Self::HoveredUiElementBorder,
+        Self::SolidBackgrounds,
+        Self::HoveredSolidBackgrounds,
+        Self::LowContrastText,
+        Self::HighContrastText,
+    ];
+
+    /// The number of the step, from 1 to 12
+    #[must_use]
+    pub const fn number(self) -> usize {
+        self as usize
+    }
+    /// The position of the step in a `[Color32; 12]` scale, from 0 to 11
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize - 1
+    }
+    /// The step with a number from 1 to 12
+    #[must_use]
+    pub const fn from_number(number: usize) -> Option<Self> {
+        if 1 <= number && number <= 12 {
+            Some(Self::ALL[number - 1])
+        } else {
+            None
+        }
+    }
+    /// The token that is taken from this step
+    #[must_use]
+    pub const fn token(self) -> Token {
+        Token::ALL[self.index()]
+    }
+}
+
+/// Semantic colors with a fixed meaning, independent of the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Status {
+    Success,
+    Warning,
+    Error,
+    Info,
+}
+
+impl Status {
+    pub const ALL: [Self; 4] = [Self::Success, Self::Warning, Self::Error, Self::Info];
+
+    /// The color the scale of this status is computed from.
+    #[must_use]
+    pub const fn theme_color(self) -> ThemeColor {
+        match self {
+            Self::Success => ThemeColor::Green,
+            Self::Warning => ThemeColor::Orange,
+            Self::Error => ThemeColor::Red,
+            Self::Info => ThemeColor::Blue,
+        }
+    }
 }
 
 impl ColorTokens {
@@ -80,9 +225,81 @@ impl ColorTokens {
         self.inverse_color
     }
     #[must_use]
+    pub const fn dark_mode(&self) -> bool {
+        self.dark_mode
+    }
+    #[must_use]
     pub const fn on_accent(&self) -> Color32 {
         self.on_accent
     }
+    /// The 12 step scale of a status color in the current mode.
+    #[must_use]
+    pub const fn status_scale(&self, status: Status) -> [Color32; 12] {
+        self.status[status as usize]
+    }
+    /// Text color of the success scale
+    #[must_use]
+    pub const fn success(&self) -> Color32 {
+        self.status[Status::Success as usize][10]
+    }
+    /// Text color of the warning scale
+    #[must_use]
+    pub const fn warning(&self) -> Color32 {
+        self.status[Status::Warning as usize][10]
+    }
+    /// Text color of the error scale
+    #[must_use]
+    pub const fn error(&self) -> Color32 {
+        self.status[Status::Error as usize][10]
+    }
+    /// Text color of the info scale
+    #[must_use]
+    pub const fn info(&self) -> Color32 {
+        self.status[Status::Info as usize][10]
+    }
+
+    /// Computes the tokens of a theme without a `Colorix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{tokens::ColorTokens, utils::WARM};
+    /// let dark = ColorTokens::from_theme(&WARM, true);
+    /// let accent = dark.solid_backgrounds();
+    /// ```
+    #[must_use]
+    pub fn from_theme(theme: &Theme, dark_mode: bool) -> Self {
+        let mut tokens = Self {
+            dark_mode,
+            ..Default::default()
+        };
+        for (i, color) in theme.iter().enumerate() {
+            tokens.update_schema(i, generate_scale(color.rgb(), dark_mode)[i]);
+        }
+        tokens.color_on_accent();
+        tokens.update_status();
+        tokens
+    }
+
+    /// The translucent variant of the tokens. Every token looks identical to the solid one
+    /// when drawn over `app_background`, e.g. for overlays, tooltips and selection fills.
+    /// `on_accent` is drawn on the accent, so it looks identical when drawn over `solid_backgrounds`.
+    #[must_use]
+    pub fn alpha_tokens(&self) -> Self {
+        let mut tokens = *self;
+        for i in 0..12 {
+            tokens.update_schema(i, alpha_color(self.get_token(i), self.app_background));
+        }
+        tokens.on_accent = alpha_color(self.on_accent, self.solid_backgrounds);
+        tokens
+    }
+
+    pub(crate) fn update_status(&mut self) {
+        for status in Status::ALL {
+            self.status[status as usize] =
+                generate_scale(status.theme_color().rgb(), self.dark_mode);
+        }
+    }
 
     pub(crate) fn color_on_accent(&mut self) {
         let lc = estimate_lc(egui::Color32::WHITE, self.solid_backgrounds);
@@ -93,11 +310,12 @@ impl ColorTokens {
             hsva.v = 0.01;
             self.on_accent = hsva.into();
         } else {
+            self.inverse_color = false;
             self.on_accent = egui::Color32::WHITE;
         }
     }
 
-    pub(crate) fn update_schema(&mut self, i: usize, fill: Color32) {
+    pub(crate) const fn update_schema(&mut self, i: usize, fill: Color32) {
         match i {
             0 => self.app_background = fill,
             1 => self.subtle_background = fill,
@@ -114,98 +332,103 @@ impl ColorTokens {
             _ => {}
         }
     }
-    pub(crate) const fn get_token(&self, i: usize) -> Color32 {
-        match i {
-            0 => self.app_background,
-            1 => self.subtle_background,
-            2 => self.ui_element_background,
-            3 => self.hovered_ui_element_background,
-            4 => self.active_ui_element_background,
-            5 => self.subtle_borders_and_separators,
-            6 => self.ui_element_border_and_focus_rings,
-            7 => self.hovered_ui_element_border,
-            8 => self.solid_backgrounds,
-            9 => self.hovered_solid_backgrounds,
-            10 => self.low_contrast_text,
-            11 => self.high_contrast_text,
-            _ => Color32::TRANSPARENT,
-        }
+    /// The color of a token.
+    #[must_use]
+    pub fn get(&self, token: Token) -> Color32 {
+        self[token]
     }
-
-    pub(crate) fn set_ctx_visuals(&self, ctx: &Context) {
-        ctx.style_mut(|style| self.set_egui_style(style));
+    /// The colors of the 12 steps, from `app_background` to `high_contrast_text`.
+    pub fn iter(&self) -> impl Iterator<Item = Color32> + '_ {
+        Step::ALL.into_iter().map(|step| self[step])
     }
-    pub(crate) fn set_ui_visuals(&self, ui: &mut Ui) {
-        self.set_egui_style(ui.style_mut());
+    // the color of the token with index `i`, in the order of `Token::ALL`
+    pub(crate) fn get_token(&self, i: usize) -> Color32 {
+        Token::ALL
+            .get(i)
+            .map_or(Color32::TRANSPARENT, |&token| self[token])
     }
 
-    pub fn set_egui_style(&self, style: &mut egui::style::Style) {
-        let shadow = if self.dark_mode {
-            Color32::from_black_alpha(96)
+    // shadows are tinted with the darkest token of the scale
+    pub(crate) fn shadow(&self) -> Color32 {
+        if self.dark_mode {
+            self.app_background.gamma_multiply(96. / 255.)
         } else {
-            Color32::from_black_alpha(25)
-        };
-        let selection = egui::style::Selection {
-            bg_fill: self.solid_backgrounds,
-            stroke: Stroke::new(1.0, self.on_accent),
-        };
-        let text_cursor = TextCursorStyle {
-            stroke: Stroke::new(2.0, self.low_contrast_text),
-            ..Default::default()
+            self.high_contrast_text.gamma_multiply(25. / 255.)
+        }
+    }
+
+    /// Maps the tokens to every color of `egui::Visuals` through `mapping`, so no stock egui color remains.
+    /// The widgets get the rounding and stroke widths of `shape`.
+    ///
+    /// Both the static and the animated styling go through this mapping.
+    ///
+    /// `override_text_color` is cleared, so text gets the colors of the widget states.
+    /// An override set by the app is dropped, set it again after applying the tokens to keep it.
+    /// Fields without a color (e.g. `handle_shape` or `interact_cursor`) are copied from `base`.
+    #[must_use]
+    pub fn to_visuals(
+        &self,
+        base: &Visuals,
+        shape: &StyleShape,
+        mapping: &TokenMapping,
+    ) -> Visuals {
+        let map = mapping;
+        let shadow = self.shadow();
+        let widget = |map: WidgetMapping, shape: WidgetShape| WidgetVisuals {
+            weak_bg_fill: self.get(map.weak_bg_fill),
+            bg_fill: self.get(map.bg_fill),
+            bg_stroke: Stroke::new(shape.bg_stroke_width, self.get(map.bg_stroke)),
+            fg_stroke: Stroke::new(shape.fg_stroke_width, self.get(map.fg_stroke)),
+            rounding: shape.rounding,
+            expansion: shape.expansion,
         };
-        let widgets = egui::style::Widgets {
-            noninteractive: WidgetVisuals {
-                weak_bg_fill: self.subtle_background,
-                bg_fill: self.subtle_background,
-                bg_stroke: Stroke::new(1.0, self.subtle_borders_and_separators), // separators, indentation lines
-                fg_stroke: Stroke::new(1.0, self.low_contrast_text), // normal text color
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
+        Visuals {
+            selection: Selection {
+                bg_fill: self.get(map.selection_bg_fill),
+                stroke: Stroke::new(shape.selection_stroke_width, self.get(map.selection_stroke)),
             },
-            inactive: WidgetVisuals {
-                weak_bg_fill: self.ui_element_background, // button background
-                bg_fill: self.ui_element_background,      // checkbox background
-                bg_stroke: Stroke::new(1.0, self.ui_element_background),
-                fg_stroke: Stroke::new(1.0, self.low_contrast_text), // button text
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
+            widgets: Widgets {
+                noninteractive: widget(map.noninteractive, shape.noninteractive),
+                inactive: widget(map.inactive, shape.inactive),
+                hovered: widget(map.hovered, shape.hovered),
+                active: widget(map.active, shape.active),
+                open: widget(map.open, shape.open),
             },
-            hovered: WidgetVisuals {
-                weak_bg_fill: self.hovered_ui_element_background,
-                bg_fill: self.hovered_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.hovered_ui_element_border), // e.g. hover over window edge or button
-                fg_stroke: Stroke::new(1.5, self.high_contrast_text),
-                rounding: Rounding::same(3.0),
-                expansion: 1.0,
+            text_cursor: TextCursorStyle {
+                stroke: Stroke::new(shape.text_cursor_width, self.get(map.text_cursor)),
+                ..base.text_cursor
             },
-            active: WidgetVisuals {
-                weak_bg_fill: self.active_ui_element_background,
-                bg_fill: self.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(2.0, self.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 1.0,
+            extreme_bg_color: self.get(map.extreme_bg_color),
+            faint_bg_color: self.get(map.faint_bg_color),
+            code_bg_color: self.get(map.code_bg_color),
+            window_fill: self.get(map.window_fill),
+            window_stroke: Stroke::new(shape.window_stroke_width, self.get(map.window_stroke)),
+            panel_fill: self.get(map.panel_fill),
+            hyperlink_color: self.get(map.hyperlink_color),
+            error_fg_color: self.error(),
+            warn_fg_color: self.warning(),
+            window_shadow: Shadow {
+                color: shadow,
+                ..base.window_shadow
             },
-            open: WidgetVisuals {
-                weak_bg_fill: self.active_ui_element_background,
-                bg_fill: self.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(1.0, self.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
+            popup_shadow: Shadow {
+                color: shadow,
+                ..base.popup_shadow
             },
-        };
-        style.visuals.selection = selection;
-        style.visuals.widgets = widgets;
-        style.visuals.text_cursor = text_cursor;
-        style.visuals.extreme_bg_color = self.app_background; // e.g. TextEdit background
-        style.visuals.faint_bg_color = self.app_background; // striped grid is originally from_additive_luminance(5)
-        style.visuals.code_bg_color = self.ui_element_background;
-        style.visuals.window_fill = self.subtle_background;
-        style.visuals.window_stroke = Stroke::new(1.0, self.subtle_borders_and_separators);
-        style.visuals.panel_fill = self.subtle_background;
-        style.visuals.hyperlink_color = self.hovered_solid_backgrounds;
-        style.visuals.window_shadow.color = shadow;
+            override_text_color: None,
+            dark_mode: self.dark_mode,
+            ..base.clone()
+        }
+    }
+
+    /// Sets the visuals of a style from the tokens with the default shape and mapping,
+    /// see [`Self::to_visuals`].
+    pub fn set_egui_style(&self, style: &mut egui::style::Style) {
+        style.visuals = self.to_visuals(
+            &style.visuals,
+            &StyleShape::default(),
+            &TokenMapping::default(),
+        );
     }
 }
 
@@ -246,6 +469,36 @@ pub enum ThemeColor {
     Custom([u8; 3]),
 }
 
+impl Index<Step> for ColorTokens {
+    type Output = Color32;
+
+    fn index(&self, step: Step) -> &Color32 {
+        &self[step.token()]
+    }
+}
+
+impl Index<Token> for ColorTokens {
+    type Output = Color32;
+
+    fn index(&self, token: Token) -> &Color32 {
+        match token {
+            Token::AppBackground => &self.app_background,
+            Token::SubtleBackground => &self.subtle_background,
+            Token::UiElementBackground => &self.ui_element_background,
+            Token::HoveredUiElementBackground => &self.hovered_ui_element_background,
+            Token::ActiveUiElementBackground => &self.active_ui_element_background,
+            Token::SubtleBordersAndSeparators => &self.subtle_borders_and_separators,
+            Token::UiElementBorderAndFocusRings => &self.ui_element_border_and_focus_rings,
+            Token::HoveredUiElementBorder => &self.hovered_ui_element_border,
+            Token::SolidBackgrounds => &self.solid_backgrounds,
+            Token::HoveredSolidBackgrounds => &self.hovered_solid_backgrounds,
+            Token::LowContrastText => &self.low_contrast_text,
+            Token::HighContrastText => &self.high_contrast_text,
+            Token::OnAccent => &self.on_accent,
+        }
+    }
+}
+
 impl ThemeColor {
     pub(crate) fn get_srgb(self) -> LinSrgb {
         LinSrgb::into_linear(self.rgb())
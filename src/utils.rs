@@ -105,7 +105,8 @@ pub const SEVENTIES: Theme = [
     ThemeColor::Gray,
 ];
 
-pub(crate) const THEMES: [Theme; 7] = [
+/// The preset themes, as offered by [`crate::Colorix::themes_dropdown`]
+pub const THEMES: [Theme; 7] = [
     EGUI_THEME,
     INDIGO_JADE,
     GRASS_BRONZE,
@@ -115,7 +116,33 @@ pub(crate) const THEMES: [Theme; 7] = [
     OFFICE_GRAY,
 ];
 
-pub(crate) const LABELS: [&str; 12] = [
+pub(crate) const PRESETS: [ThemeColor; 22] = [
+    ThemeColor::Gray,
+    ThemeColor::EguiBlue,
+    ThemeColor::Tomato,
+    ThemeColor::Red,
+    ThemeColor::Ruby,
+    ThemeColor::Crimson,
+    ThemeColor::Pink,
+    ThemeColor::Plum,
+    ThemeColor::Purple,
+    ThemeColor::Violet,
+    ThemeColor::Iris,
+    ThemeColor::Indigo,
+    ThemeColor::Blue,
+    ThemeColor::Cyan,
+    ThemeColor::Teal,
+    ThemeColor::Jade,
+    ThemeColor::Green,
+    ThemeColor::Grass,
+    ThemeColor::Brown,
+    ThemeColor::Bronze,
+    ThemeColor::Gold,
+    ThemeColor::Orange,
+];
+
+/// The names of the 12 steps of a scale, from step 1 to step 12
+pub const LABELS: [&str; 12] = [
     "app background",
     "subtle background",
     "ui element background",
@@ -0,0 +1,413 @@
+//! Drives animated `Colorix` instances with a fake clock. Every animation has to end
+//! with exactly the style of the non-animated path.
+#![allow(clippy::float_cmp)]
+
+use egui::{Context, Id, Visuals};
+use egui_colors::{
+    easing::Easing,
+    interpolation::Interpolation,
+    keyframes::{ColorTrack, Repeat},
+    tokens::{ColorTokens, ThemeColor, Token},
+    utils::{COOL, INDIGO_JADE, THEMES, WARM},
+    Colorix,
+};
+
+mod common;
+use common::{context, expected_style, run, FRAME_TIME};
+
+#[test]
+fn animation_ends_with_static_style() {
+    for dark_mode in [false, true] {
+        for (i, theme) in THEMES.iter().enumerate() {
+            let ctx = context(dark_mode);
+            let start = THEMES[(i + 1) % THEMES.len()];
+            let mut colorix = Colorix::global(&ctx, start).animated().set_time(0.5);
+            let mut frame = 0;
+
+            run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+            colorix.update_theme(&ctx, *theme);
+            run(&ctx, &mut frame, 1.0, |ctx| colorix.set_animator(ctx));
+
+            assert_eq!(
+                ctx.style().visuals,
+                expected_style(&ctx, theme, dark_mode).visuals,
+                "preset {i}, dark mode {dark_mode}"
+            );
+            assert_eq!(colorix.animator.animated_tokens, colorix.tokens);
+        }
+    }
+}
+
+#[test]
+fn every_interpolation_ends_with_static_style() {
+    let interpolations = [
+        Interpolation::Srgb,
+        Interpolation::LinearSrgb,
+        Interpolation::Oklab,
+        Interpolation::Oklch,
+    ];
+    for interpolation in interpolations {
+        let ctx = context(true);
+        let mut colorix = Colorix::global(&ctx, COOL)
+            .animated()
+            .set_time(0.5)
+            .interpolation(interpolation);
+        let mut frame = 0;
+
+        run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+        colorix.update_theme(&ctx, WARM);
+        run(&ctx, &mut frame, 0.25, |ctx| colorix.set_animator(ctx));
+        assert_ne!(
+            ctx.style().visuals,
+            expected_style(&ctx, &WARM, true).visuals
+        );
+        run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+
+        assert_eq!(
+            ctx.style().visuals,
+            expected_style(&ctx, &WARM, true).visuals,
+            "{interpolation:?}"
+        );
+    }
+}
+
+#[test]
+fn eased_and_staggered_animation_ends_with_static_style() {
+    let easings = [
+        Easing::Linear,
+        Easing::EaseInOut,
+        Easing::Cubic,
+        Easing::Spring,
+        Easing::custom(|t| t * t),
+    ];
+    for easing in easings {
+        let ctx = context(false);
+        let mut colorix = Colorix::global(&ctx, COOL)
+            .animated()
+            .set_time(0.5)
+            .easing(easing.clone())
+            .stagger(0.5);
+        let mut frame = 0;
+
+        run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+        colorix.update_theme(&ctx, WARM);
+        run(&ctx, &mut frame, 0.15, |ctx| colorix.set_animator(ctx));
+        // the backgrounds start before the text
+        let tokens = colorix.animator.animated_tokens;
+        let start = ColorTokens::from_theme(&COOL, false);
+        assert_ne!(
+            tokens.ui_element_background(),
+            start.ui_element_background(),
+            "{easing:?}"
+        );
+        assert_eq!(
+            tokens.high_contrast_text(),
+            start.high_contrast_text(),
+            "{easing:?}"
+        );
+        run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+
+        assert_eq!(
+            ctx.style().visuals,
+            expected_style(&ctx, &WARM, false).visuals,
+            "{easing:?}"
+        );
+    }
+}
+
+#[test]
+fn animated_and_static_paths_agree() {
+    let theme = [ThemeColor::Custom([18, 165, 148]); 12];
+    for dark_mode in [false, true] {
+        let animated_ctx = context(dark_mode);
+        let mut animated = Colorix::global(&animated_ctx, WARM)
+            .animated()
+            .set_time(0.5);
+        let static_ctx = context(dark_mode);
+        let mut colorix = Colorix::global(&static_ctx, WARM);
+        let mut frame = 0;
+
+        run(&animated_ctx, &mut frame, 0.1, |ctx| {
+            animated.set_animator(ctx);
+        });
+        animated.update_theme(&animated_ctx, theme);
+        colorix.update_theme(&static_ctx, theme);
+        run(&animated_ctx, &mut frame, 1.0, |ctx| {
+            animated.set_animator(ctx);
+        });
+
+        assert_eq!(animated_ctx.style().visuals, static_ctx.style().visuals);
+        assert_eq!(animated.tokens, colorix.tokens);
+    }
+}
+
+const fn shadow_alpha(visuals: &Visuals) -> u8 {
+    visuals.window_shadow.color.a()
+}
+
+#[test]
+fn global_light_dark_switch_cross_fades() {
+    let ctx = context(true);
+    let mut colorix = Colorix::global(&ctx, WARM).animated().set_time(1.0);
+    let dark = expected_style(&ctx, &WARM, true).visuals;
+    let light = expected_style(&ctx, &WARM, false).visuals;
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+    run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| colorix.set_light(ui));
+    });
+
+    run(&ctx, &mut frame, 0.25, |ctx| colorix.set_animator(ctx));
+    let visuals = ctx.style().visuals.clone();
+    assert!(visuals.dark_mode);
+    assert!(shadow_alpha(&light) < shadow_alpha(&visuals));
+    assert!(shadow_alpha(&visuals) < shadow_alpha(&dark));
+
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert!(!ctx.style().visuals.dark_mode);
+
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert_eq!(ctx.style().visuals, light);
+}
+
+fn local_frame(ctx: &Context, colorix: &mut Option<Colorix>, switch_to_light: bool) -> Visuals {
+    let mut visuals = Visuals::default();
+    egui::CentralPanel::default().show(ctx, |ui| {
+        let colorix =
+            colorix.get_or_insert_with(|| Colorix::local(ui, WARM).animated().set_time(1.0));
+        if switch_to_light {
+            colorix.set_light(ui);
+        }
+        colorix.update_locally(ui);
+        visuals = ui.style().visuals.clone();
+    });
+    visuals
+}
+
+#[test]
+fn local_light_dark_switch_cross_fades() {
+    let ctx = context(true);
+    let dark = expected_style(&ctx, &WARM, true).visuals;
+    let light = expected_style(&ctx, &WARM, false).visuals;
+    let mut colorix = None;
+    let mut visuals = Visuals::default();
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| {
+        visuals = local_frame(ctx, &mut colorix, false);
+    });
+    assert_eq!(visuals, dark);
+    run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+        visuals = local_frame(ctx, &mut colorix, true);
+    });
+
+    run(&ctx, &mut frame, 0.25, |ctx| {
+        visuals = local_frame(ctx, &mut colorix, false);
+    });
+    assert!(visuals.dark_mode);
+    assert!(shadow_alpha(&light) < shadow_alpha(&visuals));
+    assert!(shadow_alpha(&visuals) < shadow_alpha(&dark));
+
+    run(&ctx, &mut frame, 0.5, |ctx| {
+        visuals = local_frame(ctx, &mut colorix, false);
+    });
+    assert!(!visuals.dark_mode);
+
+    run(&ctx, &mut frame, 0.5, |ctx| {
+        visuals = local_frame(ctx, &mut colorix, false);
+    });
+    assert_eq!(visuals, light);
+}
+
+#[test]
+fn extra_scale_light_dark_switch_cross_fades() {
+    let ctx = context(true);
+    let mut colorix = Colorix::extra_scale(&ctx, WARM).animated().set_time(1.0);
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+    run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| colorix.set_light(ui));
+    });
+
+    run(&ctx, &mut frame, 0.25, |ctx| colorix.set_animator(ctx));
+    assert!(colorix.animator.animated_tokens.dark_mode());
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert!(!colorix.animator.animated_tokens.dark_mode());
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert_eq!(colorix.animator.animated_tokens, colorix.tokens);
+    assert_eq!(
+        colorix.tokens,
+        ColorTokens::from_theme(&WARM, false),
+        "the extra scale ends on the light scale"
+    );
+}
+
+#[test]
+fn lifecycle_events() {
+    let ctx = context(true);
+    let mut colorix = Colorix::global(&ctx, WARM).animated().set_time(0.5);
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+    assert!(!colorix.is_animating());
+    assert_eq!(colorix.animator.normalized_progress(), 1.0);
+
+    colorix.update_theme(&ctx, COOL);
+    assert!(colorix.is_animating());
+    assert!(colorix.animator.take_started());
+    assert!(!colorix.animator.take_started());
+
+    run(&ctx, &mut frame, 0.25, |ctx| colorix.set_animator(ctx));
+    let progress = colorix.animator.normalized_progress();
+    assert!(0.0 < progress && progress < 1.0);
+    assert!(!colorix.animator.take_finished());
+
+    // an interrupted transition doesn't finish
+    colorix.update_theme(&ctx, INDIGO_JADE);
+    assert!(colorix.animator.take_started());
+    run(&ctx, &mut frame, 0.25, |ctx| colorix.set_animator(ctx));
+    assert!(!colorix.animator.take_finished());
+
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert!(!colorix.is_animating());
+    assert!(colorix.animator.take_finished());
+    assert!(!colorix.animator.take_finished());
+}
+
+#[test]
+fn keyframe_tracks_follow_the_tokens() {
+    let ctx = context(true);
+    let mut colorix = Colorix::global(&ctx, WARM).animated().set_time(0.5);
+    let pulse = ColorTrack::between(Token::SolidBackgrounds, Token::HoveredSolidBackgrounds, 1.0)
+        .keyframe(0.5, Token::HighContrastText)
+        .repeat(Repeat::PingPong);
+    colorix.add_track("pulse", pulse);
+    let mut frame = 0;
+    let mut color = None;
+
+    run(&ctx, &mut frame, 0.1, |ctx| {
+        colorix.set_animator(ctx);
+        color = colorix.track_color(ctx, "pulse");
+    });
+    assert_eq!(color, Some(colorix.tokens.solid_backgrounds()));
+    assert_eq!(colorix.track_color(&ctx, "unknown"), None);
+
+    colorix.play_track(&ctx, "pulse");
+    run(&ctx, &mut frame, 0.5, |ctx| {
+        colorix.set_animator(ctx);
+        color = colorix.track_color(ctx, "pulse");
+    });
+    assert_eq!(color, Some(colorix.tokens.high_contrast_text()));
+
+    // the track is resolved against the new theme once the transition is done
+    colorix.update_theme(&ctx, COOL);
+    run(&ctx, &mut frame, 1.0, |ctx| {
+        colorix.set_animator(ctx);
+        color = colorix.track_color(ctx, "pulse");
+    });
+    assert_eq!(color, Some(colorix.tokens.high_contrast_text()));
+    assert_eq!(colorix.tokens, ColorTokens::from_theme(&COOL, true));
+
+    colorix.stop_track("pulse");
+    assert_eq!(
+        colorix.track_color(&ctx, "pulse"),
+        Some(colorix.tokens.solid_backgrounds())
+    );
+}
+
+fn tick(ctx: &Context, frame: &mut usize, seconds: f64, colorixes: &mut [&mut Colorix]) {
+    run(ctx, frame, seconds, |ctx| {
+        for colorix in colorixes.iter_mut() {
+            colorix.set_animator(ctx);
+        }
+    });
+}
+
+#[test]
+fn animators_run_independently() {
+    let ctx = context(true);
+    let mut fast = Colorix::extra_scale(&ctx, WARM).animated().set_time(0.5);
+    let mut slow = Colorix::extra_scale(&ctx, WARM)
+        .animated()
+        .set_time(1.0)
+        .animation_id("slow");
+    let mut frame = 0;
+
+    tick(&ctx, &mut frame, 0.1, &mut [&mut fast, &mut slow]);
+    fast.update_theme(&ctx, COOL);
+    slow.update_theme(&ctx, INDIGO_JADE);
+    tick(&ctx, &mut frame, 0.6, &mut [&mut fast, &mut slow]);
+    assert!(!fast.is_animating());
+    assert!(slow.is_animating());
+    assert_eq!(fast.animator.animated_tokens, fast.tokens);
+
+    tick(&ctx, &mut frame, 0.5, &mut [&mut fast, &mut slow]);
+    assert!(!slow.is_animating());
+    assert_eq!(slow.animator.animated_tokens, slow.tokens);
+    assert_eq!(slow.tokens, ColorTokens::from_theme(&INDIGO_JADE, true));
+    assert_eq!(fast.tokens, ColorTokens::from_theme(&COOL, true));
+}
+
+#[test]
+fn a_clone_gets_its_own_animation() {
+    let ctx = context(true);
+    let mut original = Colorix::extra_scale(&ctx, WARM).animated().set_time(0.5);
+    let mut frame = 0;
+
+    tick(&ctx, &mut frame, 0.1, &mut [&mut original]);
+    let mut copy = original.clone().set_time(1.0);
+    original.update_theme(&ctx, COOL);
+    copy.update_theme(&ctx, INDIGO_JADE);
+    tick(&ctx, &mut frame, 0.6, &mut [&mut original, &mut copy]);
+    assert!(!original.is_animating());
+    assert!(copy.is_animating());
+
+    // the end of the original doesn't restart the copy
+    tick(&ctx, &mut frame, 0.5, &mut [&mut original, &mut copy]);
+    assert!(!copy.is_animating());
+    assert_eq!(copy.tokens, ColorTokens::from_theme(&INDIGO_JADE, true));
+}
+
+#[test]
+fn the_animation_id_can_be_set_before_animated() {
+    let ctx = context(true);
+    let mut colorix = Colorix::extra_scale(&ctx, WARM)
+        .animation_id("fixed")
+        .animated()
+        .set_time(0.5);
+    let mut frame = 0;
+
+    tick(&ctx, &mut frame, 0.1, &mut [&mut colorix]);
+    colorix.update_theme(&ctx, COOL);
+    tick(&ctx, &mut frame, 0.25, &mut [&mut colorix]);
+    let progress = ctx.animate_value_with_time(Id::new("fixed"), 1.0, 0.5);
+    assert!(progress > 0.0 && progress < 1.0, "{progress}");
+}
+
+#[test]
+fn headless_animator_agrees_with_the_context() {
+    let ctx = context(true);
+    let mut colorix = Colorix::global(&ctx, WARM)
+        .animated()
+        .set_time(0.5)
+        .easing(Easing::Cubic)
+        .stagger(0.3);
+    let mut headless = colorix.animator.clone();
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| colorix.set_animator(ctx));
+    colorix.update_theme(&ctx, COOL);
+    headless.begin();
+    run(&ctx, &mut frame, 0.2, |ctx| colorix.set_animator(ctx));
+    let t = colorix.animator.normalized_progress() * 0.5;
+    assert_eq!(
+        headless.sample(t, &colorix.tokens),
+        colorix.animator.animated_tokens
+    );
+
+    run(&ctx, &mut frame, 0.5, |ctx| colorix.set_animator(ctx));
+    assert_eq!(headless.advance(0.5, &colorix.tokens), colorix.tokens);
+    assert!(!headless.is_animating());
+}
@@ -0,0 +1,36 @@
+//! Fixtures shared by the tests that run frames of an egui `Context`.
+
+use egui::{Context, RawInput, Style, Visuals};
+use egui_colors::{tokens::ColorTokens, Theme};
+
+pub const FRAME_TIME: f64 = 1. / 60.;
+
+pub fn context(dark_mode: bool) -> Context {
+    let ctx = Context::default();
+    ctx.set_visuals(if dark_mode {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    });
+    ctx
+}
+
+/// Runs frames until `seconds` of fake time have passed.
+pub fn run(ctx: &Context, frame: &mut usize, seconds: f64, mut f: impl FnMut(&Context)) {
+    let frames = (seconds / FRAME_TIME).ceil() as usize;
+    for _ in 0..frames {
+        *frame += 1;
+        let input = RawInput {
+            time: Some(*frame as f64 * FRAME_TIME),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, &mut f);
+    }
+}
+
+/// The style of the non-animated path.
+pub fn expected_style(ctx: &Context, theme: &Theme, dark_mode: bool) -> Style {
+    let mut style = (*ctx.style()).clone();
+    ColorTokens::from_theme(theme, dark_mode).set_egui_style(&mut style);
+    style
+}
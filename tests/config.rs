@@ -0,0 +1,89 @@
+//! Saving and restoring a `Colorix`.
+#![cfg(feature = "json")]
+
+use egui::{Context, Visuals};
+use egui_colors::{
+    config::ThemeConfig,
+    easing::Easing,
+    tokens::ThemeColor,
+    utils::{COOL, WARM},
+    ApplyTo, Colorix,
+};
+
+fn round_trip(config: &ThemeConfig) -> Result<ThemeConfig, serde_json::Error> {
+    serde_json::from_str(&serde_json::to_string(config)?)
+}
+
+#[test]
+fn easing_and_stagger_are_restored() -> Result<(), serde_json::Error> {
+    let ctx = Context::default();
+    let colorix = Colorix::global(&ctx, WARM)
+        .animated()
+        .easing(Easing::Spring)
+        .stagger(0.4);
+    let config = round_trip(&colorix.to_config())?;
+    assert_eq!(config.easing, Easing::Spring);
+
+    let restored = Colorix::from_config(&ctx, &config);
+    assert_eq!(restored.to_config().easing, Easing::Spring);
+    assert!((restored.to_config().stagger - 0.4).abs() < f32::EPSILON);
+    Ok(())
+}
+
+#[test]
+fn a_custom_easing_is_saved_as_linear() -> Result<(), serde_json::Error> {
+    let ctx = Context::default();
+    let colorix = Colorix::global(&ctx, WARM)
+        .animated()
+        .easing(Easing::custom(|t| t * t));
+    assert_eq!(round_trip(&colorix.to_config())?.easing, Easing::Linear);
+    Ok(())
+}
+
+#[test]
+fn everything_but_the_tracks_is_restored() -> Result<(), serde_json::Error> {
+    let ctx = Context::default();
+    let mut colorix = Colorix::scoped(&ctx, WARM)
+        .animation_id("sidebar")
+        .animated()
+        .set_time(0.5);
+    colorix.register_scale("danger", ThemeColor::Red);
+    let config = round_trip(&colorix.to_config())?;
+    assert_eq!(config, colorix.to_config());
+    assert_eq!(config.apply_to, ApplyTo::Scope);
+    assert!(config.animation_id.is_some());
+
+    let restored = Colorix::from_config(&ctx, &config);
+    assert_eq!(restored.to_config(), config);
+    assert_eq!(restored.scale("danger"), colorix.scale("danger"));
+    Ok(())
+}
+
+#[test]
+fn only_a_global_colorix_changes_the_context() {
+    let ctx = Context::default();
+    ctx.set_visuals(Visuals::dark());
+    let config = Colorix::scoped(&ctx, WARM).to_config();
+    let _ = Colorix::from_config(&ctx, &config);
+    assert_eq!(ctx.style().visuals, Visuals::dark());
+
+    let config = ThemeConfig {
+        apply_to: ApplyTo::Global,
+        ..config
+    };
+    let _ = Colorix::from_config(&ctx, &config);
+    assert_ne!(ctx.style().visuals, Visuals::dark());
+}
+
+#[test]
+fn the_theme_of_the_mode_is_restored() {
+    let ctx = Context::default();
+    let config = ThemeConfig {
+        mode_themes: Some([COOL, WARM]),
+        dark_mode: true,
+        ..Colorix::global(&ctx, COOL).to_config()
+    };
+    let restored = Colorix::from_config(&ctx, &config);
+    assert_eq!(restored.theme(), &WARM);
+    assert_eq!(restored.to_config().mode_themes, Some([COOL, WARM]));
+}
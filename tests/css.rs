@@ -0,0 +1,84 @@
+//! CSS custom properties of the light and dark scales.
+
+use egui::{ecolor::HexColor, Color32};
+use egui_colors::{
+    tokens::ColorTokens,
+    utils::{LABELS, THEMES},
+    Colorix,
+};
+
+fn css_color(color: Color32) -> String {
+    if color.is_opaque() {
+        HexColor::Hex6(color).to_string()
+    } else {
+        HexColor::Hex8(color).to_string()
+    }
+}
+
+// the declarations of a block, in order
+fn declarations(block: &str) -> Vec<(String, String)> {
+    block
+        .lines()
+        .filter_map(|line| line.trim().strip_suffix(';')?.split_once(": "))
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect()
+}
+
+fn expected(tokens: &ColorTokens) -> Vec<(String, String)> {
+    LABELS
+        .iter()
+        .zip(tokens.iter())
+        .map(|(label, color)| (format!("--{}", label.replace(' ', "-")), css_color(color)))
+        .chain([("--on-accent".to_owned(), css_color(tokens.on_accent()))])
+        .collect()
+}
+
+fn assert_scales(light: &str, dark: &str, theme: &egui_colors::Theme) {
+    assert_eq!(
+        declarations(light),
+        expected(&ColorTokens::from_theme(theme, false))
+    );
+    assert_eq!(
+        declarations(dark),
+        expected(&ColorTokens::from_theme(theme, true))
+    );
+}
+
+#[test]
+fn variables_are_named_after_the_labels() {
+    let css = ColorTokens::from_theme(&THEMES[0], true).css_variables("");
+    let names: Vec<String> = declarations(&css)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(names.len(), 13);
+    assert_eq!(names[0], "--app-background");
+    assert_eq!(names[11], "--high-contrast-text");
+    assert_eq!(names[12], "--on-accent");
+}
+
+#[test]
+fn media_query_layout() {
+    for theme in THEMES {
+        let css = Colorix::local_from_style(theme, true).css_variables(true);
+        let Some((light, dark)) = css.split_once("@media (prefers-color-scheme: dark) {") else {
+            panic!("no media query in {css}");
+        };
+        assert!(light.starts_with(":root {\n"));
+        assert!(dark.trim_start().starts_with(":root {"));
+        assert_scales(light, dark, &theme);
+    }
+}
+
+#[test]
+fn data_theme_layout() {
+    for theme in THEMES {
+        // the mode of the Colorix doesn't matter
+        let css = Colorix::local_from_style(theme, false).css_variables(false);
+        let Some((light, dark)) = css.split_once("[data-theme=\"dark\"] {") else {
+            panic!("no dark selector in {css}");
+        };
+        assert!(light.starts_with(":root, [data-theme=\"light\"] {\n"));
+        assert_scales(light, dark, &theme);
+    }
+}
@@ -0,0 +1,67 @@
+//! Export and import of W3C design tokens.
+#![cfg(feature = "json")]
+
+use egui::{Context, Visuals};
+use egui_colors::{
+    design_tokens::{DesignTokens, DesignTokensError},
+    tokens::{ColorTokens, ThemeColor},
+    utils::{INDIGO_JADE, WARM},
+    Colorix,
+};
+
+#[test]
+fn json_round_trip() -> Result<(), DesignTokensError> {
+    let tokens = DesignTokens::from_theme(&INDIGO_JADE);
+    assert_eq!(DesignTokens::from_json(&tokens.to_json())?, tokens);
+    Ok(())
+}
+
+#[test]
+fn rejects_missing_tokens() {
+    let json = DesignTokens::from_theme(&WARM)
+        .to_json()
+        .replace("\"low-contrast-text\"", "\"low-contrast\"");
+    assert!(matches!(
+        DesignTokens::from_json(&json),
+        Err(DesignTokensError::MissingToken(token)) if token == "light.low-contrast-text"
+    ));
+}
+
+#[test]
+fn rejects_invalid_colors() {
+    let tokens = DesignTokens::from_theme(&WARM);
+    let hex = tokens.dark.app_background().to_hex();
+    let json = tokens.to_json().replace(&hex[..7], "#12a59");
+    assert!(matches!(
+        DesignTokens::from_json(&json),
+        Err(DesignTokensError::InvalidColor { value, .. }) if value == "#12a59"
+    ));
+}
+
+#[test]
+fn restores_a_colorix_in_both_modes() -> Result<(), DesignTokensError> {
+    let mut theme = WARM;
+    theme[11] = ThemeColor::Custom([18, 165, 148]);
+    let tokens = DesignTokens::from_json(&DesignTokens::from_theme(&theme).to_json())?;
+    assert_eq!(tokens.theme(true)?, theme);
+
+    for dark_mode in [false, true] {
+        let ctx = Context::default();
+        ctx.set_visuals(if dark_mode {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        });
+        let colorix = Colorix::from_design_tokens(&ctx, &tokens)?;
+        assert_eq!(colorix.theme(), &theme);
+        assert_eq!(colorix.tokens, ColorTokens::from_theme(&theme, dark_mode));
+    }
+
+    let mut without_scales = tokens;
+    without_scales.scales.clear();
+    assert!(matches!(
+        without_scales.theme(false),
+        Err(DesignTokensError::MissingScale(token)) if token == "light.app-background"
+    ));
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! Parsing colors from text.
+
+use egui_colors::{
+    parse::{parse_theme, ParseColorError, ParseThemeError},
+    tokens::ThemeColor,
+    utils::THEMES,
+};
+
+fn parse(s: &str) -> Result<ThemeColor, ParseColorError> {
+    s.parse()
+}
+
+#[test]
+fn hex_colors() {
+    assert_eq!(parse("#e8d207"), Ok(ThemeColor::Custom([232, 210, 7])));
+    assert_eq!(parse("#E8D207"), Ok(ThemeColor::Custom([232, 210, 7])));
+    assert_eq!(parse("#f80"), Ok(ThemeColor::Custom([255, 136, 0])));
+    assert_eq!(parse("  #fff "), Ok(ThemeColor::Custom([255, 255, 255])));
+}
+
+#[test]
+fn hex_colors_with_alpha_are_rejected() {
+    for s in [
+        "#ffff",
+        "#ffffffff",
+        "#ffffff80",
+        "#ff",
+        "#fffff",
+        "#",
+        "#ggg",
+    ] {
+        assert_eq!(parse(s), Err(ParseColorError::InvalidHex(s.to_string())));
+    }
+}
+
+#[test]
+fn rgb_function() {
+    assert_eq!(
+        parse("rgb(232, 210, 7)"),
+        Ok(ThemeColor::Custom([232, 210, 7]))
+    );
+    assert_eq!(
+        parse("RGB(232 210 7)"),
+        Ok(ThemeColor::Custom([232, 210, 7]))
+    );
+    assert_eq!(
+        parse("rgb(100%, 0%, 50%)"),
+        Ok(ThemeColor::Custom([255, 0, 128]))
+    );
+    for s in ["rgb(256, 0, 0)", "rgb(-1, 0, 0)", "rgb(0, 0, 101%)"] {
+        assert_eq!(
+            parse(s),
+            Err(ParseColorError::InvalidFunction(s.to_string()))
+        );
+    }
+}
+
+#[test]
+fn oklch_and_okhsl_functions() {
+    assert!(parse("oklch(0.7 0.15 250)").is_ok());
+    assert!(parse("oklch(70% 0.15 250deg)").is_ok());
+    assert!(parse("okhsl(250 50% 60%)").is_ok());
+    // the lightness of white and black
+    assert_eq!(
+        parse("oklch(1 0 0)"),
+        Ok(ThemeColor::Custom([255, 255, 255]))
+    );
+    assert_eq!(parse("okhsl(0 0 0)"), Ok(ThemeColor::Custom([0, 0, 0])));
+}
+
+#[test]
+fn wrong_argument_counts_are_rejected() {
+    for s in [
+        "rgb(1, 2)",
+        "rgb(1, 2, 3, 4)",
+        "oklch(0.7 0.15)",
+        "oklch(0.7 0.15 250 1)",
+        "okhsl()",
+        "okhsl(250 50% 60% 1)",
+        "oklch(a b c)",
+        "hsl(250 50% 60%)",
+    ] {
+        assert_eq!(
+            parse(s),
+            Err(ParseColorError::InvalidFunction(s.to_string()))
+        );
+    }
+}
+
+#[test]
+fn preset_names_are_case_insensitive() {
+    assert_eq!(parse("tomato"), Ok(ThemeColor::Tomato));
+    assert_eq!(parse("TOMATO"), Ok(ThemeColor::Tomato));
+    assert_eq!(parse("eguiblue"), Ok(ThemeColor::EguiBlue));
+    assert_eq!(
+        parse("nope"),
+        Err(ParseColorError::UnknownName("nope".to_string()))
+    );
+}
+
+#[test]
+fn display_parses_back() {
+    for color in [
+        ThemeColor::Custom([232, 210, 7]),
+        ThemeColor::Custom([0, 0, 0]),
+        ThemeColor::EguiBlue,
+        ThemeColor::Gray,
+    ] {
+        assert_eq!(parse(&color.to_string()), Ok(color));
+    }
+    assert_eq!(ThemeColor::Custom([232, 210, 7]).to_string(), "#e8d207");
+}
+
+#[test]
+fn errors_name_the_input() {
+    assert_eq!(
+        ParseColorError::InvalidHex("#ffff".to_string()).to_string(),
+        "invalid hex color `#ffff`"
+    );
+    assert_eq!(
+        ParseColorError::InvalidFunction("rgb(1)".to_string()).to_string(),
+        "invalid color function `rgb(1)`"
+    );
+    assert_eq!(
+        ParseColorError::UnknownName("nope".to_string()).to_string(),
+        "unknown color `nope`"
+    );
+}
+
+#[test]
+fn a_copied_theme_parses_back() {
+    let mut theme = [ThemeColor::Gray; 12];
+    theme[2] = ThemeColor::EguiBlue;
+    theme[8] = ThemeColor::Custom([232, 210, 7]);
+    theme[9] = ThemeColor::Custom([0, 255, 18]);
+    // the text that "Copy theme to clipboard" writes
+    assert_eq!(parse_theme(&format!("{theme:#?}")), Ok(theme));
+    assert_eq!(parse_theme(&format!("{theme:?}")), Ok(theme));
+    for theme in THEMES {
+        assert_eq!(parse_theme(&format!("{theme:#?}")), Ok(theme));
+    }
+}
+
+#[test]
+fn a_theme_needs_12_colors() {
+    let eleven = format!("{:?}", [ThemeColor::Gray; 11]);
+    assert_eq!(
+        parse_theme(&eleven),
+        Err(ParseThemeError::WrongColorCount(11))
+    );
+    let thirteen = format!("{:?}", [ThemeColor::Gray; 13]);
+    assert_eq!(
+        parse_theme(&thirteen),
+        Err(ParseThemeError::WrongColorCount(13))
+    );
+    assert_eq!(parse_theme(""), Err(ParseThemeError::WrongColorCount(0)));
+}
+
+#[test]
+fn invalid_theme_colors() {
+    assert_eq!(
+        parse_theme("[Custom([232, 210])]"),
+        Err(ParseThemeError::InvalidCustom)
+    );
+    assert_eq!(
+        parse_theme("[Custom([232, 210, 256])]"),
+        Err(ParseThemeError::InvalidCustom)
+    );
+    assert_eq!(
+        parse_theme("[Gray, Nope]"),
+        Err(ParseThemeError::InvalidColor(ParseColorError::UnknownName(
+            "Nope".to_string()
+        )))
+    );
+}
@@ -0,0 +1,168 @@
+//! Scales computed without a `Context`.
+
+use egui::Color32;
+use egui_colors::{
+    scales::{
+        alpha_color, generate_alpha_scale, generate_scale, generate_scale_okhsl, generate_scale_rgb,
+    },
+    tokens::{ColorTokens, Status, Step, ThemeColor},
+    utils::{THEMES, WARM},
+    Colorix,
+};
+
+// how egui blends a premultiplied color over an opaque background, in gamma space
+fn over(color: Color32, background: Color32) -> [u8; 3] {
+    let a = f32::from(color.a()) / 255.;
+    let [r, g, b] = [0, 1, 2].map(|i| {
+        f32::from(background[i])
+            .mul_add(1. - a, f32::from(color[i]))
+            .round() as u8
+    });
+    [r, g, b]
+}
+
+fn assert_looks_solid(alpha: Color32, background: Color32, solid: Color32) {
+    let shown = over(alpha, background);
+    for i in 0..3 {
+        assert!(
+            shown[i].abs_diff(solid[i]) <= 1,
+            "{alpha:?} over {background:?} is {shown:?}, expected {solid:?}"
+        );
+    }
+}
+
+#[test]
+fn scales_are_the_steps_of_the_tokens() {
+    for dark_mode in [false, true] {
+        for theme in THEMES {
+            let colorix = Colorix::local_from_style(theme, dark_mode);
+            for step in Step::ALL {
+                let scale = generate_scale(theme[step.index()].rgb(), dark_mode);
+                assert_eq!(
+                    scale[step.index()],
+                    colorix.tokens[step],
+                    "{theme:?} {step:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn scale_functions_agree() {
+    for dark_mode in [false, true] {
+        for color in THEMES.concat() {
+            let scale = generate_scale(color.rgb(), dark_mode);
+            let rgb = generate_scale_rgb(color.rgb(), dark_mode);
+            let okhsl = generate_scale_okhsl(color.rgb(), dark_mode);
+            for i in 0..12 {
+                assert_eq!(scale[i].to_array()[..3], rgb[i], "{color:?} {i}");
+                let [hue, saturation, lightness] = okhsl[i];
+                assert!((0.0..=360.).contains(&hue), "{color:?} {i}");
+                let parsed: Result<ThemeColor, _> =
+                    format!("okhsl({hue} {saturation} {lightness})").parse();
+                assert_eq!(parsed, Ok(ThemeColor::Custom(rgb[i])), "{color:?} {i}");
+            }
+        }
+    }
+}
+
+#[test]
+fn alpha_colors_composite_to_the_solid_color() {
+    let colors = [
+        Color32::from_rgb(18, 165, 148),
+        Color32::BLACK,
+        Color32::WHITE,
+        Color32::from_rgb(255, 0, 128),
+    ];
+    for color in colors {
+        for background in colors {
+            assert_looks_solid(alpha_color(color, background), background, color);
+        }
+    }
+}
+
+#[test]
+fn alpha_scales_composite_to_the_solid_scale() {
+    for preset in THEMES.concat() {
+        for dark_mode in [false, true] {
+            let scale = generate_scale(preset.rgb(), dark_mode);
+            let alpha = generate_alpha_scale(preset.rgb(), dark_mode);
+            for (alpha, solid) in alpha.iter().zip(scale) {
+                assert_looks_solid(*alpha, scale[0], solid);
+            }
+        }
+    }
+}
+
+#[test]
+fn alpha_tokens_composite_to_the_solid_tokens() {
+    for theme in THEMES {
+        for dark_mode in [false, true] {
+            let tokens = ColorTokens::from_theme(&theme, dark_mode);
+            let alpha = tokens.alpha_tokens();
+            assert_looks_solid(
+                alpha.ui_element_background(),
+                tokens.app_background(),
+                tokens.ui_element_background(),
+            );
+            assert_looks_solid(
+                alpha.high_contrast_text(),
+                tokens.app_background(),
+                tokens.high_contrast_text(),
+            );
+            assert_looks_solid(
+                alpha.on_accent(),
+                tokens.solid_backgrounds(),
+                tokens.on_accent(),
+            );
+        }
+    }
+}
+
+#[test]
+fn status_scales_follow_the_mode() {
+    let ctx = egui::Context::default();
+    ctx.set_visuals(egui::Visuals::dark());
+    let mut colorix = Colorix::global(&ctx, WARM);
+    let error = Status::Error.theme_color().rgb();
+    assert_eq!(
+        colorix.tokens.status_scale(Status::Error),
+        generate_scale(error, true)
+    );
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| colorix.set_light(ui));
+    });
+    assert_eq!(
+        colorix.tokens.status_scale(Status::Error),
+        generate_scale(error, false)
+    );
+}
+
+#[test]
+fn named_scales_follow_the_mode() {
+    let ctx = egui::Context::default();
+    ctx.set_visuals(egui::Visuals::dark());
+    let mut colorix = Colorix::global(&ctx, WARM).animated().set_time(0.5);
+    colorix.register_scale("danger", ThemeColor::Red);
+    colorix.register_scale("chart1", ThemeColor::Custom([18, 165, 148]));
+
+    assert_eq!(
+        colorix.scale("danger"),
+        generate_scale(ThemeColor::Red.rgb(), true)
+    );
+    assert_eq!(colorix.scale("unknown"), [Color32::TRANSPARENT; 12]);
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| colorix.set_light(ui));
+    });
+    assert_eq!(
+        colorix.scale("danger"),
+        generate_scale(ThemeColor::Red.rgb(), false)
+    );
+    assert_eq!(
+        colorix.scale("chart1"),
+        generate_scale([18, 165, 148], false)
+    );
+}
@@ -0,0 +1,158 @@
+//! Themes that apply to a part of the ui: `Colorix::scope`, `update_locally` and `show_scoped`.
+
+use egui::{Context, Id, Style, Visuals};
+use egui_colors::{
+    tokens::{ColorTokens, ThemeColor},
+    utils::{COOL, INDIGO_JADE, WARM},
+    Colorix, Theme,
+};
+use std::sync::Arc;
+
+mod common;
+use common::{context, expected_style, run, FRAME_TIME};
+
+// shows two scoped colorixes next to each other in the same ui
+fn scoped_frame(ctx: &Context, scopes: &mut [Colorix; 2]) -> [(Id, Arc<Style>); 2] {
+    let mut shown = [(Id::NULL, ctx.style()), (Id::NULL, ctx.style())];
+    egui::CentralPanel::default().show(ctx, |ui| {
+        for (colorix, shown) in scopes.iter_mut().zip(&mut shown) {
+            colorix.scope(ui, |ui| *shown = (ui.id(), ui.style().clone()));
+        }
+    });
+    shown
+}
+
+#[test]
+fn scopes_are_stable_and_cached() {
+    let ctx = context(true);
+    let mut scopes = [
+        Colorix::scoped(&ctx, WARM).animated().set_time(0.5),
+        Colorix::scoped(&ctx, COOL),
+    ];
+    let mut shown = [(Id::NULL, ctx.style()), (Id::NULL, ctx.style())];
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| {
+        shown = scoped_frame(ctx, &mut scopes);
+    });
+    let before = shown.clone();
+    run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+        shown = scoped_frame(ctx, &mut scopes);
+    });
+    assert_ne!(shown[0].0, shown[1].0);
+    for (before, shown) in before.iter().zip(&shown) {
+        assert_eq!(before.0, shown.0);
+        assert!(Arc::ptr_eq(&before.1, &shown.1));
+    }
+    assert_eq!(
+        shown[1].1.visuals,
+        expected_style(&ctx, &COOL, true).visuals
+    );
+
+    scopes[0].update_theme(&ctx, INDIGO_JADE);
+    run(&ctx, &mut frame, 0.25, |ctx| {
+        shown = scoped_frame(ctx, &mut scopes);
+    });
+    assert!(scopes[0].is_animating());
+    assert_ne!(shown[0].1.visuals, before[0].1.visuals);
+    assert!(Arc::ptr_eq(&before[1].1, &shown[1].1));
+
+    run(&ctx, &mut frame, 0.5, |ctx| {
+        shown = scoped_frame(ctx, &mut scopes);
+    });
+    assert_eq!(
+        shown[0].1.visuals,
+        expected_style(&ctx, &INDIGO_JADE, true).visuals
+    );
+    assert_eq!(shown[0].0, before[0].0);
+    // the scopes leave the rest of the ui alone
+    assert_eq!(ctx.style().visuals, Visuals::dark());
+}
+
+fn local_frame(ctx: &Context, colorix: &mut Option<Colorix>) -> Arc<Style> {
+    let mut style = ctx.style();
+    egui::CentralPanel::default().show(ctx, |ui| {
+        let colorix =
+            colorix.get_or_insert_with(|| Colorix::local(ui, WARM).animated().set_time(0.5));
+        colorix.update_locally(ui);
+        style = ui.style().clone();
+    });
+    style
+}
+
+#[test]
+fn a_local_style_is_reused_once_the_animation_ends() {
+    let ctx = context(true);
+    let mut colorix = None;
+    let mut style = ctx.style();
+    let mut frame = 0;
+
+    run(&ctx, &mut frame, 0.1, |ctx| {
+        style = local_frame(ctx, &mut colorix);
+    });
+    if let Some(colorix) = &mut colorix {
+        colorix.update_theme(&ctx, COOL);
+    }
+    run(&ctx, &mut frame, 0.6, |ctx| {
+        style = local_frame(ctx, &mut colorix);
+    });
+    assert_eq!(style.visuals, expected_style(&ctx, &COOL, true).visuals);
+
+    let before = style.clone();
+    run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+        style = local_frame(ctx, &mut colorix);
+    });
+    assert!(Arc::ptr_eq(&before, &style));
+}
+
+#[test]
+fn show_scoped_restores_the_parent_style() {
+    for dark_mode in [false, true] {
+        let ctx = context(dark_mode);
+        let mut visuals = [Visuals::default(), Visuals::default(), Visuals::default()];
+        let mut frame = 0;
+
+        run(&ctx, &mut frame, 2. * FRAME_TIME, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                Colorix::show_scoped(ui, COOL, |ui| {
+                    Colorix::show_scoped(ui, WARM, |ui| {
+                        visuals[1] = ui.style().visuals.clone();
+                    });
+                    visuals[0] = ui.style().visuals.clone();
+                });
+                visuals[2] = ui.style().visuals.clone();
+            });
+        });
+        assert_eq!(visuals[0], expected_style(&ctx, &COOL, dark_mode).visuals);
+        assert_eq!(visuals[1], expected_style(&ctx, &WARM, dark_mode).visuals);
+        assert_eq!(visuals[2], ctx.style().visuals);
+    }
+}
+
+#[test]
+fn show_scoped_keeps_the_recent_themes_only() {
+    let ctx = context(true);
+    let mut frame = 0;
+    let mut visuals = Visuals::default();
+
+    // a theme that follows a color picker
+    for red in 0..40 {
+        run(&ctx, &mut frame, FRAME_TIME, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                Colorix::show_scoped(ui, [ThemeColor::Custom([red, 100, 50]); 12], |ui| {
+                    visuals = ui.style().visuals.clone();
+                });
+            });
+        });
+    }
+    let theme = [ThemeColor::Custom([39, 100, 50]); 12];
+    assert_eq!(visuals, expected_style(&ctx, &theme, true).visuals);
+    let cached = ctx.data_mut(|data| {
+        data.get_temp::<Vec<((Theme, bool), ColorTokens)>>(Id::new("egui_colors scoped themes"))
+    });
+    let Some(cached) = cached else {
+        panic!("no scoped themes in the egui memory");
+    };
+    assert_eq!(cached.len(), 16);
+    assert_eq!(cached.last().map(|(key, _)| *key), Some((theme, true)));
+}
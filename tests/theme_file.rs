@@ -0,0 +1,128 @@
+//! Reading and writing theme files, and applying them to a `Colorix`.
+#![cfg(feature = "toml")]
+
+use egui::{Context, RawInput};
+use egui_colors::{
+    parse::ParseColorError,
+    theme_file::{ThemeFile, ThemeFileError, THEME_FILE_VERSION},
+    tokens::ThemeColor,
+    utils::WARM,
+    Colorix,
+};
+use std::error::Error;
+
+fn frame(ctx: &Context, mut f: impl FnMut(&mut egui::Ui)) {
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, &mut f);
+    });
+}
+
+#[test]
+fn edits_replace_the_mode_overrides_of_a_file() -> Result<(), Box<dyn Error>> {
+    let ctx = Context::default();
+    ctx.set_visuals(egui::Visuals::dark());
+    let mut colorix = Colorix::global(&ctx, WARM);
+    let file = ThemeFile::from_toml(
+        r#"
+        version = 1
+        name = "Gray with a dark override"
+        colors = ["Gray", "Gray", "Gray", "Gray", "Gray", "Gray",
+                  "Gray", "Gray", "Gray", "Gray", "Gray", "Gray"]
+
+        [dark]
+        app_background = "Indigo"
+        "#,
+    )?;
+    colorix.update_theme_file(&ctx, &file);
+    assert_eq!(colorix.theme()[0], ThemeColor::Indigo);
+
+    frame(&ctx, |ui| {
+        colorix.twelve_from_custom(ui);
+        colorix.set_light(ui);
+    });
+    let custom = colorix.theme()[0];
+    assert!(matches!(custom, ThemeColor::Custom(_)));
+    assert_eq!(colorix.theme(), &[custom; 12]);
+    assert_eq!(
+        colorix.css_variables(true),
+        Colorix::local_from_style([custom; 12], false).css_variables(true)
+    );
+    Ok(())
+}
+
+fn example() -> ThemeFile {
+    let mut file = ThemeFile::new("Warm", WARM);
+    file.author = Some("Jane Doe".to_string());
+    file.theme[11] = ThemeColor::Custom([18, 165, 148]);
+    file.dark[8] = Some(ThemeColor::Orange);
+    file.light[0] = Some(ThemeColor::Custom([250, 250, 250]));
+    file
+}
+
+const GRAY: &str = r#""Gray", "Gray", "Gray", "Gray", "Gray", "Gray""#;
+
+fn with_colors(version: u32, colors: &str, overrides: &str) -> String {
+    format!("version = {version}\nname = \"Test\"\ncolors = [{colors}]\n{overrides}")
+}
+
+#[test]
+fn toml_round_trip() -> Result<(), ThemeFileError> {
+    let file = example();
+    assert_eq!(ThemeFile::from_toml(&file.to_toml()?)?, file);
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_round_trip() -> Result<(), ThemeFileError> {
+    let file = example();
+    assert_eq!(ThemeFile::from_json(&file.to_json()?)?, file);
+    Ok(())
+}
+
+#[test]
+fn rejects_unsupported_versions() {
+    for version in [0, THEME_FILE_VERSION + 1] {
+        let toml = with_colors(version, &format!("{GRAY}, {GRAY}"), "");
+        assert!(matches!(
+            ThemeFile::from_toml(&toml),
+            Err(ThemeFileError::UnsupportedVersion(v)) if v == version
+        ));
+    }
+}
+
+#[test]
+fn rejects_a_wrong_color_count() {
+    let toml = with_colors(1, GRAY, "");
+    assert!(matches!(
+        ThemeFile::from_toml(&toml),
+        Err(ThemeFileError::WrongColorCount(6))
+    ));
+}
+
+#[test]
+fn rejects_unknown_tokens() {
+    let toml = with_colors(1, &format!("{GRAY}, {GRAY}"), "[dark]\naccent = \"Gold\"");
+    assert!(matches!(
+        ThemeFile::from_toml(&toml),
+        Err(ThemeFileError::UnknownToken(key)) if key == "accent"
+    ));
+}
+
+#[test]
+fn rejects_invalid_colors_with_the_reason() {
+    let toml = with_colors(
+        1,
+        &format!("{GRAY}, {GRAY}"),
+        "[light]\nsolid_backgrounds = \"#12a59\"",
+    );
+    let Err(err) = ThemeFile::from_toml(&toml) else {
+        panic!("an invalid hex color is accepted");
+    };
+    assert!(matches!(
+        &err,
+        ThemeFileError::InvalidColor { slot, value, source: ParseColorError::InvalidHex(_) }
+            if slot == "solid_backgrounds" && value == "#12a59"
+    ));
+    assert!(err.source().is_some());
+}
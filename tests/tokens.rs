@@ -0,0 +1,119 @@
+//! Mapping the tokens to egui `Visuals`.
+
+use egui::{Color32, Context, Visuals};
+use egui_colors::{
+    mapping::TokenMapping,
+    shape::StyleShape,
+    tokens::{ColorTokens, Step, Token},
+    utils::{THEMES, WARM},
+    Colorix,
+};
+
+fn colors(v: &Visuals) -> Vec<Color32> {
+    let mut colors = vec![
+        v.hyperlink_color,
+        v.faint_bg_color,
+        v.extreme_bg_color,
+        v.code_bg_color,
+        v.warn_fg_color,
+        v.error_fg_color,
+        v.window_shadow.color,
+        v.window_fill,
+        v.window_stroke.color,
+        v.panel_fill,
+        v.popup_shadow.color,
+        v.text_cursor.stroke.color,
+        v.selection.bg_fill,
+        v.selection.stroke.color,
+    ];
+    let w = &v.widgets;
+    for w in [
+        &w.noninteractive,
+        &w.inactive,
+        &w.hovered,
+        &w.active,
+        &w.open,
+    ] {
+        colors.extend([
+            w.weak_bg_fill,
+            w.bg_fill,
+            w.bg_stroke.color,
+            w.fg_stroke.color,
+        ]);
+    }
+    colors
+}
+
+#[test]
+fn every_visuals_color_comes_from_the_tokens() {
+    for theme in THEMES {
+        for (dark_mode, mut base) in [(false, Visuals::light()), (true, Visuals::dark())] {
+            base.override_text_color = Some(Color32::RED);
+            let tokens = ColorTokens::from_theme(&theme, dark_mode);
+            let visuals = tokens.to_visuals(&base, &StyleShape::pill(), &TokenMapping::default());
+
+            let shadow = if dark_mode {
+                tokens.app_background().gamma_multiply(96. / 255.)
+            } else {
+                tokens.high_contrast_text().gamma_multiply(25. / 255.)
+            };
+            let mut allowed: Vec<Color32> = tokens.iter().collect();
+            allowed.extend([tokens.on_accent(), tokens.warning(), tokens.error(), shadow]);
+            for color in colors(&visuals) {
+                assert!(allowed.contains(&color), "{color:?} in {theme:?}");
+            }
+            assert_eq!(visuals.warn_fg_color, tokens.warning());
+            assert_eq!(visuals.error_fg_color, tokens.error());
+            assert_eq!(visuals.window_shadow.color, shadow);
+            assert_eq!(visuals.dark_mode, dark_mode);
+            assert_eq!(visuals.override_text_color, None);
+            assert_eq!(visuals.handle_shape, base.handle_shape);
+            assert_eq!(
+                visuals.widgets.inactive.rounding,
+                StyleShape::pill().inactive.rounding
+            );
+        }
+    }
+}
+
+#[test]
+fn the_mapping_belongs_to_the_colorix() {
+    let ctx = Context::default();
+    ctx.style_mut(|style| style.visuals = Visuals::dark());
+    let mut colorix = Colorix::global(&ctx, WARM);
+    let mut mapping = TokenMapping::default();
+    mapping.inactive.bg_stroke = Token::UiElementBorderAndFocusRings;
+    colorix.set_mapping(&ctx, mapping);
+
+    assert_eq!(colorix.mapping(), &mapping);
+    let tokens = ColorTokens::from_theme(&WARM, true);
+    assert_eq!(
+        ctx.style().visuals.widgets.inactive.bg_stroke.color,
+        tokens.ui_element_border_and_focus_rings()
+    );
+    // the tokens stay plain colors, another mapping gives other visuals
+    let visuals = tokens.to_visuals(
+        &Visuals::dark(),
+        &StyleShape::default(),
+        &TokenMapping::default(),
+    );
+    assert_eq!(
+        visuals.widgets.inactive.bg_stroke.color,
+        tokens.ui_element_background()
+    );
+}
+
+#[test]
+fn steps_index_the_scale() {
+    let tokens = ColorTokens::from_theme(&WARM, false);
+    let scale: Vec<Color32> = tokens.iter().collect();
+    for (i, step) in Step::ALL.into_iter().enumerate() {
+        assert_eq!(step.index(), i);
+        assert_eq!(Step::from_number(step.number()), Some(step));
+        assert_eq!(tokens[step], scale[step.index()]);
+        assert_eq!(tokens[step], tokens.get(step.token()));
+    }
+    assert_eq!(Step::from_number(0), None);
+    assert_eq!(Step::from_number(13), None);
+    assert_eq!(tokens[Token::OnAccent], tokens.on_accent());
+}